@@ -29,14 +29,21 @@
 use std::collections::HashMap;
 use std::fs::{create_dir_all, File};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::thread;
 use std::time::Instant;
 
-use clap::{command, Parser};
+use clap::Parser;
 use pbf_font_tools::freetype::{Face, Library};
-use pbf_font_tools::{get_named_font_stack, glyph_range_for_face, Glyphs};
+use pbf_font_tools::rustybuzz::{Feature, Language};
+use pbf_font_tools::{
+    get_named_font_stack, glyph_range_for_face_lossy_with_options, shape_labels, Glyphs,
+    RenderOptions, ShapedFont,
+};
 use protobuf::{CodedOutputStream, Message};
+use serde::Deserialize;
 use spmc::{channel, Receiver};
 
 static TOTAL_GLYPHS_RENDERED: AtomicUsize = AtomicUsize::new(0);
@@ -54,6 +61,85 @@ struct Args {
     /// Overwrites existing glyphs. By default, glyph generation will be skipped for any range with a matching file in the output directory. Note that the contents of the file are not inspected; only the name.
     #[arg(long)]
     overwrite: bool,
+    /// Path to a file of labels to pre-shape (one per line), for complex scripts. For each font, the shaped glyphs are written to a "<font name> Shaped" subdirectory, along with a substitutions.json file mapping each label to the string which renders it.
+    #[arg(long = "labels")]
+    labels_path: Option<PathBuf>,
+    /// Path to a file of font variants to render as additional fontstacks, such as synthetic bold or oblique styles. The file should contain a JSON list like so: [{"font": "Foo-Regular", "embolden": 0.04}], where "font" is the file name of a font in the source directory (without the extension).
+    #[arg(long = "variants")]
+    variants_path: Option<PathBuf>,
+    /// Trims empty rows and columns from the edges of glyphs, and omits the bitmaps of blank glyphs like spaces. This makes the output smaller, but no longer byte for byte compatible with node-fontnik.
+    #[arg(long)]
+    trim: bool,
+}
+
+/// A variant of a source font to render as a fontstack of its own.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VariantSpec {
+    /// The file name of the source font, without the extension.
+    font: String,
+    /// The fontstack name. By default, this is derived from the face and the synthetic style,
+    /// e.g. "Foo Bold (synthetic)".
+    name: Option<String>,
+    /// The face to render, for font collections.
+    #[serde(default)]
+    face_index: isize,
+    /// The synthetic bold strength, as a fraction of the em size.
+    #[serde(default)]
+    embolden: f64,
+    /// The synthetic oblique shear (the tangent of the slant angle).
+    #[serde(default)]
+    oblique: f64,
+    /// OpenType features to apply, like "tnum" or "smcp".
+    #[serde(default)]
+    features: Vec<String>,
+    /// The language used to select language-specific glyphs, like "ja" or "zh-Hant".
+    language: Option<String>,
+}
+
+impl VariantSpec {
+    /// Returns the options to render the variant with, or a description of the invalid setting.
+    fn render_options(&self) -> Result<RenderOptions, String> {
+        let features = self
+            .features
+            .iter()
+            .map(|feature| {
+                Feature::from_str(feature)
+                    .map_err(|_| format!("invalid OpenType feature {feature:?}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut options = RenderOptions::new()
+            .with_embolden(self.embolden)
+            .with_oblique(self.oblique)
+            .with_features(features);
+        if let Some(name) = &self.name {
+            options = options.with_name(name.clone());
+        }
+        if let Some(language) = &self.language {
+            let language = Language::from_str(language)
+                .map_err(|_| format!("invalid language {language:?}"))?;
+            options = options.with_language(language);
+        }
+        Ok(options)
+    }
+}
+
+/// Prints an error and exits, for invalid input which should not produce a backtrace.
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("Error: {message}");
+    std::process::exit(1);
+}
+
+/// A font to render into a subdirectory of the output directory.
+struct RenderJob {
+    /// The path to the source font.
+    path: PathBuf,
+    /// The name of the subdirectory: the file stem of the font,
+    /// or the fontstack name of a variant.
+    name: String,
+    /// The face to render for variants. Every face is rendered for plain fonts.
+    face_index: Option<isize>,
+    options: RenderOptions,
 }
 
 /// Combines glyphs for all fonts listed in `font_names` in `font_path` into a single stack
@@ -102,27 +188,37 @@ fn render_worker(
     overwrite: bool,
     radius: usize,
     cutoff: f64,
-    rx: Receiver<Option<(PathBuf, PathBuf)>>,
+    labels: &[String],
+    rx: Receiver<Option<RenderJob>>,
 ) {
     let lib = Library::init().expect("Unable to initialize FreeType");
 
-    while let Ok(Some((path, stem))) = rx.recv() {
-        let out_dir = base_out_dir.join(stem.to_str().expect("Unable to extract file stem"));
+    while let Ok(Some(job)) = rx.recv() {
+        let path = &job.path;
+        let out_dir = base_out_dir.join(&job.name);
         create_dir_all(&out_dir).expect("Unable to create output directory");
 
-        println!("Processing {}", path.display());
+        if job.face_index.is_some() {
+            println!("Processing {} as {}", path.display(), job.name);
+        } else {
+            println!("Processing {}", path.display());
+        }
 
         // Load the font once to save useless I/O
         // FIXME: lib.new_face is called twice for face_index=0
         //        instead, call it once, create a pre-allocated vector of faces for num_faces count
         //        add the already open 0th, and add all remaining ones to it
-        let face = lib.new_face(&path, 0).expect("Unable to load font");
-        let num_faces = face.num_faces() as usize;
-        let faces: Vec<Face> = (0..num_faces)
-            .map(|face_index| {
-                lib.new_face(&path, face_index as isize)
-                    .expect("Unable to load face")
-            })
+        let face_indices: Vec<isize> = match job.face_index {
+            Some(face_index) => vec![face_index],
+            None => {
+                let face = lib.new_face(path, 0).expect("Unable to load font");
+                (0..face.num_faces() as isize).collect()
+            }
+        };
+        let num_faces = face_indices.len();
+        let faces: Vec<Face> = face_indices
+            .iter()
+            .map(|&face_index| lib.new_face(path, face_index).expect("Unable to load face"))
             .collect();
 
         let mut start = 0;
@@ -140,14 +236,22 @@ fn render_worker(
             } else {
                 let mut glyphs = Glyphs::new();
 
-                for (face_index, face) in faces.iter().enumerate() {
-                    if let Ok(stack) = glyph_range_for_face(face, start, end, 24, radius, cutoff) {
-                        glyphs_rendered += stack.glyphs.len();
-                        glyphs.stacks.push(stack);
-                    } else {
-                        println!(
-                            "ERROR: Failed to render fontstack for face {face_index} in {path_str}",
-                        );
+                for (face_index, face) in face_indices.iter().zip(&faces) {
+                    match glyph_range_for_face_lossy_with_options(face, start, end, &job.options) {
+                        Ok((stack, errors)) => {
+                            for (char_code, e) in errors {
+                                println!(
+                                    "WARNING: Skipped glyph U+{char_code:04X} for face {face_index} in {path_str}: {e}",
+                                );
+                            }
+                            glyphs_rendered += stack.glyphs.len();
+                            glyphs.stacks.push(stack);
+                        }
+                        Err(e) => {
+                            println!(
+                                "ERROR: Failed to render fontstack for face {face_index} in {path_str}: {e}",
+                            );
+                        }
                     }
                 }
 
@@ -170,16 +274,144 @@ fn render_worker(
             );
         }
 
+        if job.face_index.is_none() && !labels.is_empty() {
+            // Code points are assigned per face, so only the first face of a collection is used
+            let shaped_dir = base_out_dir.join(format!("{} Shaped", job.name));
+            if !overwrite && shaped_dir.join("substitutions.json").exists() {
+                println!("Skipped shaping labels in {path_str}");
+            } else {
+                match shape_labels(path, 0, labels, 24, radius, cutoff) {
+                    Ok(shaped) => {
+                        if !shaped.unsupported.is_empty() {
+                            println!(
+                                "Skipped {} label(s) not covered by {path_str}",
+                                shaped.unsupported.len()
+                            );
+                        }
+                        // A stack without any glyphs would only shadow the fonts after it
+                        if !shaped.substitutions.is_empty() {
+                            write_shaped_font(&shaped_dir, &shaped);
+                            println!(
+                                "Shaped {} label(s) into {} glyph(s) in {path_str}",
+                                shaped.substitutions.len(),
+                                shaped.stack.glyphs.len()
+                            );
+                            glyphs_rendered += shaped.stack.glyphs.len();
+                        }
+                    }
+                    Err(e) => {
+                        println!("ERROR: Failed to shape labels for {path_str}: {e}");
+                    }
+                }
+            }
+        }
+
         TOTAL_GLYPHS_RENDERED.fetch_add(glyphs_rendered, Ordering::Relaxed);
     }
 }
 
+/// Writes the glyph ranges of a shaped font and its substitution table to `out_dir`.
+fn write_shaped_font(out_dir: &Path, shaped: &ShapedFont) {
+    create_dir_all(out_dir).expect("Unable to create output directory");
+
+    for (start, end, glyphs) in shaped.ranges() {
+        let mut file = File::create(out_dir.join(format!("{start}-{end}.pbf")))
+            .expect("Unable to create file");
+        let mut cos = CodedOutputStream::new(&mut file);
+        glyphs.write_to(&mut cos).expect("Unable to write");
+        cos.flush().expect("Unable to flush");
+    }
+
+    let file = File::create(out_dir.join("substitutions.json")).expect("Unable to create file");
+    serde_json::to_writer_pretty(file, &shaped.substitutions)
+        .expect("Unable to write substitutions");
+}
+
 fn main() {
     let args = Args::parse();
 
     let font_dir = &args.font_dir;
     let out_dir = &args.out_dir;
 
+    let labels: Arc<Vec<String>> = Arc::new(
+        args.labels_path
+            .as_ref()
+            .map(|path| {
+                std::fs::read_to_string(path)
+                    .expect("Unable to read labels")
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    );
+
+    let mut font_paths = HashMap::new();
+    for dir_entry in font_dir
+        .read_dir()
+        .expect("Unable to open font directory")
+        .flatten()
+    {
+        let path = dir_entry.path();
+
+        if let (Some(stem), Some(extension)) = (path.file_stem(), path.extension()) {
+            if path.is_file() && (["otf", "ttf", "ttc"].contains(&extension.to_str().unwrap())) {
+                let name = stem
+                    .to_str()
+                    .expect("Unable to extract file stem")
+                    .to_string();
+                font_paths.insert(name, path);
+            }
+        }
+    }
+
+    // Validate the variants before rendering anything, so that a typo doesn't abort the run
+    // halfway through
+    let mut variants = Vec::new();
+    if let Some(path) = &args.variants_path {
+        let data = std::fs::read(path).expect("Unable to read variant spec.");
+        let specs: Vec<VariantSpec> = serde_json::from_slice(&data).unwrap_or_else(|e| {
+            exit_with_error(&format!(
+                "Unable to parse variant spec {}: {e}",
+                path.display()
+            ))
+        });
+        let lib = Library::init().expect("Unable to initialize FreeType");
+
+        for (index, variant) in specs.into_iter().enumerate() {
+            let describe = || {
+                let name = variant.name.as_deref().unwrap_or(&variant.font);
+                format!("Invalid variant #{} ({name:?})", index + 1)
+            };
+            let Some(path) = font_paths.get(&variant.font) else {
+                exit_with_error(&format!("{}: unknown font {:?}", describe(), variant.font));
+            };
+            let options = variant
+                .render_options()
+                .unwrap_or_else(|e| exit_with_error(&format!("{}: {e}", describe())));
+            let face = lib.new_face(path, variant.face_index).unwrap_or_else(|e| {
+                exit_with_error(&format!(
+                    "{}: unable to load face {} of {}: {e}",
+                    describe(),
+                    variant.face_index,
+                    path.display()
+                ))
+            });
+            let options = options.with_trim(args.trim);
+            let Some(name) = options.stack_name(&face) else {
+                exit_with_error(&format!("{}: the face has no family name", describe()));
+            };
+
+            variants.push(RenderJob {
+                path: path.clone(),
+                name,
+                face_index: Some(variant.face_index),
+                options,
+            });
+        }
+    }
+
     let (mut tx, rx) = channel();
     let num_threads = num_cpus::get();
     println!("Starting {num_threads} worker threads...");
@@ -188,25 +420,26 @@ fn main() {
         .map(|_| {
             let out_dir = out_dir.clone();
             let rx = rx.clone();
-            thread::spawn(move || render_worker(&out_dir, args.overwrite, 8, 0.25, rx))
+            let labels = labels.clone();
+            thread::spawn(move || render_worker(&out_dir, args.overwrite, 8, 0.25, &labels, rx))
         })
         .collect();
 
     let render_start = Instant::now();
 
-    for dir_entry in font_dir
-        .read_dir()
-        .expect("Unable to open font directory")
-        .flatten()
-    {
-        let path = dir_entry.path();
+    for (name, path) in font_paths {
+        tx.send(Some(RenderJob {
+            path,
+            name,
+            face_index: None,
+            options: RenderOptions::new().with_trim(args.trim),
+        }))
+        .expect("Unable to push job to thread worker");
+    }
 
-        if let (Some(stem), Some(extension)) = (path.file_stem(), path.extension()) {
-            if path.is_file() && (["otf", "ttf", "ttc"].contains(&extension.to_str().unwrap())) {
-                tx.send(Some((path.clone(), PathBuf::from(stem))))
-                    .expect("Unable to push job to thread worker");
-            }
-        }
+    for job in variants {
+        tx.send(Some(job))
+            .expect("Unable to push job to thread worker");
     }
 
     for _ in 0..num_threads {
@@ -0,0 +1,109 @@
+//! This binary crate provides a CLI utility for converting a directory of SVG icons into
+//! signed distance field sprite sheets for renderers such as MapLibre GL, which can recolour
+//! SDF icons (`"sdf": true` in the sprite index) at runtime.
+//!
+//! SVGs are rasterised with [resvg](https://github.com/linebender/resvg) (pure Rust, so there
+//! are no system dependencies), and the signed distance fields are computed with
+//! [sdf_glyph_renderer](https://github.com/stadiamaps/sdf_font_tools/tree/main/sdf_glyph_renderer).
+//!
+//! ## Usage
+//!
+//! This tool will create `out_dir` if necessary, and will write `sprite.png` and `sprite.json`,
+//! plus a variant for each additional pixel ratio (`sprite@2x.png` and `sprite@2x.json` by
+//! default). Icons are named after their file names, without the `.svg` extension.
+//!
+//! ```
+//! $ build_sdf_sprites /path/to/svg_dir /path/to/out_dir
+//! ```
+
+mod sprite;
+
+use std::fs::{create_dir_all, read};
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::sprite::{render_icon, write_sprite, SdfSettings};
+
+#[derive(Parser, Debug)]
+#[command(version, author, about)]
+struct Args {
+    /// Sets the source directory to be scanned for SVG icons.
+    svg_dir: PathBuf,
+    /// Sets the output directory in which the sprite sheets and their indexes will be placed.
+    out_dir: PathBuf,
+    /// Sets the base name of the output files.
+    #[arg(long, default_value = "sprite")]
+    name: String,
+    /// Sets the pixel ratios to generate sprite sheets for (comma separated).
+    #[arg(long, value_delimiter = ',', default_value = "1,2")]
+    pixel_ratios: Vec<usize>,
+    /// Sets the number of pixels of buffer around each icon (at a pixel ratio of 1).
+    #[arg(long, default_value_t = 3)]
+    buffer: usize,
+    /// Sets the distance (in px, at a pixel ratio of 1) from the outline which is captured by the SDF.
+    #[arg(long, default_value_t = 8)]
+    radius: usize,
+    /// Sets the fraction of the encoded range used for distances inside the icon.
+    #[arg(long, default_value_t = 0.25)]
+    cutoff: f64,
+}
+
+fn main() {
+    let args = Args::parse();
+    let settings = SdfSettings {
+        buffer: args.buffer,
+        radius: args.radius,
+        cutoff: args.cutoff,
+    };
+
+    let mut svg_paths: Vec<PathBuf> = args
+        .svg_dir
+        .read_dir()
+        .expect("Unable to open SVG directory")
+        .flatten()
+        .map(|dir_entry| dir_entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path.extension().and_then(|extension| extension.to_str()) == Some("svg")
+        })
+        .collect();
+    svg_paths.sort();
+
+    let svgs: Vec<(String, Vec<u8>)> = svg_paths
+        .iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .expect("Unable to extract file stem")
+                .to_string();
+            (name, read(path).expect("Unable to read SVG"))
+        })
+        .collect();
+
+    create_dir_all(&args.out_dir).expect("Unable to create output directory");
+
+    for pixel_ratio in args.pixel_ratios {
+        let icons: Vec<_> = svgs
+            .iter()
+            .filter_map(
+                |(name, data)| match render_icon(name, data, pixel_ratio, settings) {
+                    Ok(icon) => Some(icon),
+                    Err(e) => {
+                        println!("WARNING: Skipped {name} at @{pixel_ratio}x: {e}");
+                        None
+                    }
+                },
+            )
+            .collect();
+
+        write_sprite(&icons, pixel_ratio, &args.out_dir, &args.name)
+            .expect("Unable to write sprite sheet");
+        println!(
+            "Wrote {} icon(s) at @{pixel_ratio}x to {}",
+            icons.len(),
+            args.out_dir.display()
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use std::fs::write;
+use std::path::Path;
+
+use resvg::tiny_skia::{IntSize, Pixmap, Transform};
+use resvg::usvg;
+use sdf_glyph_renderer::{clamp_to_u8, BitmapGlyph, SdfGlyphError};
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+/// The space left between icons in the sprite sheet, in px.
+const PADDING: usize = 1;
+
+#[derive(Debug, Error)]
+pub enum SpriteError {
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Unable to parse SVG: {0}")]
+    SvgError(#[from] usvg::Error),
+
+    #[error("SDF error: {0}")]
+    SdfError(#[from] SdfGlyphError),
+
+    #[error("Unable to encode PNG: {0}")]
+    PngError(String),
+
+    #[error("Invalid image size for {0}")]
+    InvalidSize(String),
+}
+
+/// SDF rendering settings, in px at a pixel ratio of 1. These are scaled up for higher
+/// pixel ratios, so that all variants of an icon look the same.
+#[derive(Clone, Copy, Debug)]
+pub struct SdfSettings {
+    pub buffer: usize,
+    pub radius: usize,
+    pub cutoff: f64,
+}
+
+/// An icon rendered as an SDF, encoded with [`clamp_to_u8`].
+#[derive(Debug)]
+pub struct SdfIcon {
+    pub name: String,
+
+    /// The buffered width of the icon in px.
+    pub width: usize,
+
+    /// The buffered height of the icon in px.
+    pub height: usize,
+
+    pub sdf: Vec<u8>,
+}
+
+/// Rasterises an SVG image at the given pixel ratio, and renders the result as an SDF.
+///
+/// The shape of the icon is taken from its alpha channel; colours are ignored, as the renderer
+/// will recolour SDF icons anyway.
+pub fn render_icon(
+    name: &str,
+    svg_data: &[u8],
+    pixel_ratio: usize,
+    settings: SdfSettings,
+) -> Result<SdfIcon, SpriteError> {
+    let tree = usvg::Tree::from_data(svg_data, &usvg::Options::default())?;
+    let scale = pixel_ratio as f32;
+    let width = (tree.size().width() * scale).ceil() as u32;
+    let height = (tree.size().height() * scale).ceil() as u32;
+
+    let mut pixmap =
+        Pixmap::new(width, height).ok_or_else(|| SpriteError::InvalidSize(name.to_string()))?;
+    resvg::render(
+        &tree,
+        Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+
+    let alpha: Vec<u8> = pixmap.pixels().iter().map(|pixel| pixel.alpha()).collect();
+    let buffer = settings.buffer * pixel_ratio;
+    let bitmap = BitmapGlyph::from_unbuffered(&alpha, width as usize, height as usize, buffer)?;
+    let sdf = clamp_to_u8(
+        &bitmap.render_sdf(settings.radius * pixel_ratio),
+        settings.cutoff,
+    )?;
+
+    Ok(SdfIcon {
+        name: name.to_string(),
+        width: width as usize + buffer * 2,
+        height: height as usize + buffer * 2,
+        sdf,
+    })
+}
+
+/// Packs icons into a sheet using a simple shelf algorithm (tallest icons first).
+///
+/// Returns the size of the sheet and the top left position of each icon.
+fn pack(icons: &[SdfIcon]) -> ((usize, usize), Vec<(usize, usize)>) {
+    // Aim for a roughly square sheet, but never narrower than the widest icon
+    let area: usize = icons
+        .iter()
+        .map(|icon| (icon.width + PADDING) * (icon.height + PADDING))
+        .sum();
+    let widest = icons.iter().map(|icon| icon.width).max().unwrap_or(0);
+    let sheet_width = widest.max((area as f64).sqrt().ceil() as usize).max(1);
+
+    let mut order: Vec<usize> = (0..icons.len()).collect();
+    order.sort_by(|a, b| {
+        icons[*b]
+            .height
+            .cmp(&icons[*a].height)
+            .then_with(|| icons[*a].name.cmp(&icons[*b].name))
+    });
+
+    let mut positions = vec![(0, 0); icons.len()];
+    let (mut x, mut y, mut shelf_height) = (0, 0, 0);
+    for index in order {
+        let icon = &icons[index];
+        if x > 0 && x + icon.width > sheet_width {
+            x = 0;
+            y += shelf_height + PADDING;
+            shelf_height = 0;
+        }
+        positions[index] = (x, y);
+        x += icon.width + PADDING;
+        shelf_height = shelf_height.max(icon.height);
+    }
+
+    ((sheet_width, (y + shelf_height).max(1)), positions)
+}
+
+/// Packs icons into a sprite sheet, writing `<name>.png` and `<name>.json` (with an `@<n>x`
+/// suffix for pixel ratios other than 1) to `out_dir`.
+///
+/// The SDF is stored in the alpha channel of the PNG, and every icon in the index is
+/// marked with `"sdf": true`.
+pub fn write_sprite(
+    icons: &[SdfIcon],
+    pixel_ratio: usize,
+    out_dir: &Path,
+    name: &str,
+) -> Result<(), SpriteError> {
+    let ((sheet_width, sheet_height), positions) = pack(icons);
+
+    // Black with the SDF as alpha (which is also valid premultiplied data)
+    let mut data = vec![0u8; sheet_width * sheet_height * 4];
+    let mut index = Map::new();
+    for (icon, (x, y)) in icons.iter().zip(positions) {
+        for row in 0..icon.height {
+            for column in 0..icon.width {
+                data[((y + row) * sheet_width + x + column) * 4 + 3] =
+                    icon.sdf[row * icon.width + column];
+            }
+        }
+
+        index.insert(
+            icon.name.clone(),
+            json!({
+                "width": icon.width,
+                "height": icon.height,
+                "x": x,
+                "y": y,
+                "pixelRatio": pixel_ratio,
+                "sdf": true,
+            }),
+        );
+    }
+
+    let size = IntSize::from_wh(sheet_width as u32, sheet_height as u32)
+        .ok_or_else(|| SpriteError::InvalidSize(name.to_string()))?;
+    let png = Pixmap::from_vec(data, size)
+        .ok_or_else(|| SpriteError::InvalidSize(name.to_string()))?
+        .encode_png()
+        .map_err(|e| SpriteError::PngError(e.to_string()))?;
+
+    let suffix = if pixel_ratio == 1 {
+        String::new()
+    } else {
+        format!("@{pixel_ratio}x")
+    };
+    write(out_dir.join(format!("{name}{suffix}.png")), png)?;
+    write(
+        out_dir.join(format!("{name}{suffix}.json")),
+        serde_json::to_vec_pretty(&Value::Object(index)).expect("JSON values always serialize"),
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use resvg::tiny_skia::Pixmap;
+    use serde_json::Value;
+
+    use super::{pack, render_icon, write_sprite, SdfIcon, SdfSettings};
+
+    const SETTINGS: SdfSettings = SdfSettings {
+        buffer: 3,
+        radius: 8,
+        cutoff: 0.25,
+    };
+
+    const SQUARE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="12" height="10">
+        <rect x="2" y="2" width="8" height="6" fill="red"/>
+    </svg>"#;
+
+    #[test]
+    fn test_render_icon() {
+        let icon = render_icon("square", SQUARE.as_bytes(), 1, SETTINGS).unwrap();
+        assert_eq!((icon.width, icon.height), (18, 16));
+        assert_eq!(icon.sdf.len(), 18 * 16);
+
+        // Inside the square (well past the edge, so the encoded value saturates)
+        assert!(icon.sdf[8 * 18 + 9] > 191);
+        // Outside the square (at the edge of the buffer)
+        assert!(icon.sdf[0] < 191);
+
+        let icon = render_icon("square", SQUARE.as_bytes(), 2, SETTINGS).unwrap();
+        assert_eq!((icon.width, icon.height), (36, 32));
+
+        assert!(render_icon("broken", b"<svg", 1, SETTINGS).is_err());
+    }
+
+    #[test]
+    fn test_pack() {
+        let icons: Vec<SdfIcon> = [(10, 20), (30, 5), (7, 7), (12, 12), (40, 3)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (width, height))| SdfIcon {
+                name: format!("icon-{i}"),
+                width,
+                height,
+                sdf: vec![0; width * height],
+            })
+            .collect();
+        let ((sheet_width, sheet_height), positions) = pack(&icons);
+
+        for (i, (a, (ax, ay))) in icons.iter().zip(positions.iter()).enumerate() {
+            assert!(ax + a.width <= sheet_width);
+            assert!(ay + a.height <= sheet_height);
+            for (b, (bx, by)) in icons.iter().zip(positions.iter()).skip(i + 1) {
+                let overlaps = ax < &(bx + b.width)
+                    && bx < &(ax + a.width)
+                    && ay < &(by + b.height)
+                    && by < &(ay + a.height);
+                assert!(!overlaps, "{} overlaps {}", a.name, b.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_sprite() {
+        let out_dir =
+            std::env::temp_dir().join(format!("build_sdf_sprites_test_{}", std::process::id()));
+        std::fs::create_dir_all(&out_dir).unwrap();
+
+        for pixel_ratio in [1, 2] {
+            let icons = vec![
+                render_icon("square", SQUARE.as_bytes(), pixel_ratio, SETTINGS).unwrap(),
+                render_icon("another", SQUARE.as_bytes(), pixel_ratio, SETTINGS).unwrap(),
+            ];
+            write_sprite(&icons, pixel_ratio, &out_dir, "sprite").unwrap();
+        }
+
+        let index: Value =
+            serde_json::from_slice(&std::fs::read(out_dir.join("sprite@2x.json")).unwrap())
+                .unwrap();
+        let square = &index["square"];
+        assert_eq!(square["sdf"], Value::Bool(true));
+        assert_eq!(square["pixelRatio"], 2);
+        assert_eq!(square["width"], 36);
+
+        let sheet =
+            Pixmap::decode_png(&std::fs::read(out_dir.join("sprite.png")).unwrap()).unwrap();
+        let index: Value =
+            serde_json::from_slice(&std::fs::read(out_dir.join("sprite.json")).unwrap()).unwrap();
+        let (x, y) = (
+            index["another"]["x"].as_u64().unwrap() as u32,
+            index["another"]["y"].as_u64().unwrap() as u32,
+        );
+        assert_eq!(index["another"]["pixelRatio"], 1);
+        assert!(sheet.pixel(x + 9, y + 8).unwrap().alpha() > 191);
+
+        std::fs::remove_dir_all(&out_dir).unwrap();
+    }
+}
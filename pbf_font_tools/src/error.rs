@@ -1,4 +1,5 @@
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum PbfFontError {
     #[error("Sub-process error: {0}")]
     JoinError(#[from] tokio::task::JoinError),
@@ -7,9 +8,20 @@ pub enum PbfFontError {
     #[cfg(feature = "freetype")]
     #[error("SDF glyph error: {0}")]
     SdfGlyphError(#[from] sdf_glyph_renderer::SdfGlyphError),
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
     #[error("Font family name is not set")]
     MissingFontFamilyName,
     #[cfg(feature = "freetype")]
     #[error("Freetype error: {0}")]
     FreetypeError(#[from] crate::freetype::Error),
+    #[cfg(feature = "freetype")]
+    #[error("No source font is known for {0}")]
+    MissingFontSource(String),
+    #[cfg(feature = "shaping")]
+    #[error("Unable to parse font data for shaping")]
+    InvalidFontData,
+    #[cfg(feature = "shaping")]
+    #[error("Too many distinct glyphs to fit in the Private Use Area")]
+    PrivateUseAreaExhausted,
 }
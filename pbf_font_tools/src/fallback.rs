@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::fs::{create_dir_all, rename, write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use futures::future::join_all;
+use protobuf::Message;
+use tokio::task::spawn_blocking;
+
+use crate::error::PbfFontError;
+use crate::freetype;
+use crate::ft_generate::{face_name, glyph_range_for_face_with_options};
+use crate::options::RenderOptions;
+use crate::proto::glyphs::Glyphs;
+use crate::tools::{combine_font_stack, load_glyphs};
+
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Initialising FreeType is relatively expensive, so each (blocking) worker thread
+    /// keeps a library around for all the ranges it renders.
+    static LIBRARY: Result<freetype::Library, freetype::Error> = freetype::Library::init();
+}
+
+/// Runs `f` with this thread's FreeType library.
+fn with_library<T>(
+    f: impl FnOnce(&freetype::Library) -> Result<T, PbfFontError>,
+) -> Result<T, PbfFontError> {
+    LIBRARY.with(|lib| f(lib.as_ref().map_err(|e| *e)?))
+}
+
+/// The location of a single face within a source font file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FontSource {
+    /// The path to the TrueType/OpenType font (or collection).
+    pub path: PathBuf,
+
+    /// The index of the face within the font file. This is always zero unless the file is a
+    /// font collection (`.ttc`).
+    pub face_index: isize,
+}
+
+/// Renders glyph ranges on demand from source fonts when no pre-rendered PBF exists.
+///
+/// The renderer maps fontstack names (the same names used for PBF directories) to a
+/// [`FontSource`]. Glyphs are rendered with the same settings `build_pbf_glyphs` uses
+/// unless configured otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct FallbackRenderer {
+    sources: HashMap<String, FontSource>,
+    options: RenderOptions,
+    write_back: bool,
+}
+
+impl FallbackRenderer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps a fontstack name to a face in a source font file.
+    #[must_use]
+    pub fn with_font<P: Into<PathBuf>>(mut self, name: String, path: P, face_index: isize) -> Self {
+        self.sources.insert(
+            name,
+            FontSource {
+                path: path.into(),
+                face_index,
+            },
+        );
+        self
+    }
+
+    /// Scans a directory for fonts, mapping every face found to its fontstack name.
+    ///
+    /// Fontstack names are derived the same way as in
+    /// [`glyph_range_for_face`](crate::glyph_range_for_face):
+    /// the family name followed by the style name.
+    /// Fonts which FreeType cannot open are skipped;
+    /// use [`FallbackRenderer::with_font_dir_lossy`] to find out which.
+    pub fn with_font_dir<P: AsRef<Path>>(self, font_dir: P) -> Result<Self, PbfFontError> {
+        Ok(self.with_font_dir_lossy(font_dir)?.0)
+    }
+
+    /// A version of [`FallbackRenderer::with_font_dir`] which also returns the path and error
+    /// for every font (or face) that was skipped.
+    ///
+    /// An error is only returned if the directory itself cannot be read.
+    pub fn with_font_dir_lossy<P: AsRef<Path>>(
+        mut self,
+        font_dir: P,
+    ) -> Result<(Self, Vec<(PathBuf, PbfFontError)>), PbfFontError> {
+        let mut skipped = Vec::new();
+        let entries = font_dir.as_ref().read_dir()?;
+
+        with_library(|lib| {
+            for dir_entry in entries.flatten() {
+                let path = dir_entry.path();
+                let is_font = path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| ["otf", "ttf", "ttc"].contains(&extension));
+                if !path.is_file() || !is_font {
+                    continue;
+                }
+
+                let num_faces = match lib.new_face(&path, 0) {
+                    Ok(face) => face.num_faces(),
+                    Err(e) => {
+                        skipped.push((path, e.into()));
+                        continue;
+                    }
+                };
+                for face_index in 0..num_faces as isize {
+                    match lib.new_face(&path, face_index) {
+                        Ok(face) => {
+                            if let Some(name) = face_name(&face) {
+                                self.sources.entry(name).or_insert(FontSource {
+                                    path: path.clone(),
+                                    face_index,
+                                });
+                            }
+                        }
+                        Err(e) => skipped.push((path.clone(), e.into())),
+                    }
+                }
+            }
+
+            Ok(())
+        })?;
+
+        Ok((self, skipped))
+    }
+
+    /// Sets the font size, `radius` and `cutoff` used for rendering.
+    /// See [`glyph_range_for_face`](crate::glyph_range_for_face) for details.
+    #[must_use]
+    pub fn with_render_settings(mut self, size: usize, radius: usize, cutoff: f64) -> Self {
+        self.options = self.options.with_render_settings(size, radius, cutoff);
+        self
+    }
+
+    /// Sets the [`RenderOptions`] used for rendering, so that ranges rendered on the fly match
+    /// those rendered ahead of time with the same options (by `build_pbf_glyphs`, for example).
+    ///
+    /// Any name set in the options is ignored, since rendered ranges are always named after the
+    /// requested font.
+    #[must_use]
+    pub fn with_render_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Writes rendered ranges back to the glyph directory, so that they only
+    /// need to be rendered once.
+    #[must_use]
+    pub fn with_write_back(mut self, write_back: bool) -> Self {
+        self.write_back = write_back;
+        self
+    }
+
+    /// Returns the source font for a fontstack name, if one is known.
+    #[must_use]
+    pub fn source(&self, font_name: &str) -> Option<&FontSource> {
+        self.sources.get(font_name)
+    }
+
+    /// Returns an iterator over all fontstack names which can be rendered.
+    pub fn font_names(&self) -> impl Iterator<Item = &str> {
+        self.sources.keys().map(String::as_str)
+    }
+
+    /// Renders a single glyph range for the named font.
+    ///
+    /// The resulting font stack is named `font_name`, regardless of the names embedded in the
+    /// font, so that it matches what would have been loaded from disk.
+    pub fn render(&self, font_name: &str, start: u32, end: u32) -> Result<Glyphs, PbfFontError> {
+        let Some(source) = self.sources.get(font_name) else {
+            return Err(PbfFontError::MissingFontSource(font_name.to_string()));
+        };
+
+        let mut stack = with_library(|lib| {
+            let face = lib.new_face(&source.path, source.face_index)?;
+            glyph_range_for_face_with_options(&face, start, end, &self.options)
+        })?;
+        stack.set_name(font_name.to_string());
+
+        let mut glyphs = Glyphs::new();
+        glyphs.stacks.push(stack);
+        Ok(glyphs)
+    }
+}
+
+/// Loads a single font PBF slice from disk, rendering it from the source font
+/// if the file does not exist.
+///
+/// Fonts are assumed to be stored in `<font_path>/<font_name>/<start>-<end>.pbf`.
+/// If the renderer is configured to write back, the rendered range is saved to that path.
+///
+/// This is the fallback-aware counterpart of [`load_glyphs`].
+/// Font stacks served through a `service::DirectoryStore` with a fallback renderer
+/// use this for every font in the stack.
+pub async fn load_or_render_glyphs<P: AsRef<Path>>(
+    font_path: P,
+    font_name: &str,
+    start: u32,
+    end: u32,
+    fallback: Arc<FallbackRenderer>,
+) -> Result<Glyphs, PbfFontError> {
+    let font_dir = font_path.as_ref().join(font_name);
+    let full_path = font_dir.join(format!("{start}-{end}.pbf"));
+
+    if fallback.source(font_name).is_none() || tokio::fs::try_exists(&full_path).await? {
+        return load_glyphs(font_path, font_name, start, end).await;
+    }
+
+    let font_name = font_name.to_string();
+    spawn_blocking(move || {
+        let glyphs = fallback.render(&font_name, start, end)?;
+
+        if fallback.write_back {
+            // Write to a (unique) temporary file first so that concurrent readers never see
+            // a partial range.
+            create_dir_all(&font_dir)?;
+            let temp_path = font_dir.join(format!(
+                "{start}-{end}.pbf.{}-{}.tmp",
+                std::process::id(),
+                TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            write(&temp_path, glyphs.write_to_bytes()?)?;
+            rename(temp_path, full_path)?;
+        }
+
+        Ok(glyphs)
+    })
+    .await?
+}
+
+/// Generates a single combined font stack for the set of fonts provided, rendering any ranges
+/// which have not been pre-rendered from their source fonts.
+///
+/// This is the fallback-aware counterpart of
+/// [`get_named_font_stack`](crate::get_named_font_stack), loading each font with
+/// [`load_or_render_glyphs`].
+pub async fn get_named_font_stack_or_render<P: AsRef<Path>>(
+    font_path: P,
+    font_names: &[&str],
+    stack_name: String,
+    start: u32,
+    end: u32,
+    fallback: Arc<FallbackRenderer>,
+) -> Result<Glyphs, PbfFontError> {
+    if font_names.is_empty() {
+        return Err(PbfFontError::MissingFontFamilyName);
+    }
+
+    let glyph_data =
+        join_all(font_names.iter().map(|font| {
+            load_or_render_glyphs(font_path.as_ref(), font, start, end, fallback.clone())
+        }))
+        .await
+        .into_iter()
+        .filter_map(|g| g.ok())
+        .collect();
+
+    combine_font_stack(glyph_data, stack_name, start, end).await
+}
+
+/// The fallback-aware counterpart of [`get_font_stack`](crate::get_font_stack).
+/// See [`get_named_font_stack_or_render`] for details.
+pub async fn get_font_stack_or_render<P: AsRef<Path>>(
+    font_path: P,
+    font_names: &[&str],
+    start: u32,
+    end: u32,
+    fallback: Arc<FallbackRenderer>,
+) -> Result<Glyphs, PbfFontError> {
+    let stack_name = font_names.join(", ");
+    get_named_font_stack_or_render(font_path, font_names, stack_name, start, end, fallback).await
+}
@@ -1,9 +1,21 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
 
-use sdf_glyph_renderer::{clamp_to_u8, render_sdf_from_face};
+use futures::future::join_all;
+use sdf_glyph_renderer::{
+    clamp_to_u8, render_sdf_from_face, render_sdf_from_glyph_index,
+    render_sdf_from_glyph_index_with_options, set_pixel_size, GlyphRenderOptions, SdfGlyph,
+    SdfGlyphError,
+};
+use tokio::task::spawn_blocking;
 
 use crate::error::PbfFontError;
-use crate::{freetype, Fontstack, Glyph, Glyphs};
+use crate::{freetype, Fontstack, Glyph, Glyphs, RenderOptions, Rounding};
 
 /// Renders a single glyph for the given font face into a Glyph message.
 pub fn render_sdf_glyph(
@@ -14,19 +26,60 @@ pub fn render_sdf_glyph(
     cutoff: f64,
 ) -> Result<Glyph, PbfFontError> {
     let glyph = render_sdf_from_face(face, char_code, buffer, radius)?;
+    encode_glyph(&glyph, char_code, cutoff, Rounding::default())
+}
 
+/// Renders a single glyph for the given font face into a Glyph message, looking it up by its
+/// index in the face rather than by character code.
+///
+/// The glyph is given the caller's choice of `id`, the code point renderers will request it by.
+/// This is useful for glyphs which have no character code of their own, such as the output of a
+/// shaper, or for remapping icon fonts.
+pub fn render_sdf_glyph_from_index(
+    face: &freetype::Face,
+    glyph_index: u32,
+    id: u32,
+    buffer: usize,
+    radius: usize,
+    cutoff: f64,
+) -> Result<Glyph, PbfFontError> {
+    let glyph = render_sdf_from_glyph_index(face, glyph_index, buffer, radius)?;
+    encode_glyph(&glyph, id, cutoff, Rounding::default())
+}
+
+/// Encodes a rendered glyph into a Glyph message with the given ID, rounding its metrics
+/// to whole pixels with the given policy.
+pub(crate) fn encode_glyph(
+    glyph: &SdfGlyph,
+    id: u32,
+    cutoff: f64,
+    rounding: Rounding,
+) -> Result<Glyph, PbfFontError> {
     let mut result = Glyph::new();
-    result.set_id(char_code);
-    result.set_bitmap(clamp_to_u8(&glyph.sdf, cutoff)?);
+    result.set_id(id);
+    let bitmap = clamp_to_u8(&glyph.sdf, cutoff)?;
+    if !bitmap.is_empty() {
+        result.set_bitmap(bitmap);
+    }
     result.set_width(glyph.metrics.width as u32);
     result.set_height(glyph.metrics.height as u32);
     result.set_left(glyph.metrics.left_bearing);
-    result.set_top(glyph.metrics.top_bearing - glyph.metrics.ascender);
-    result.set_advance(glyph.metrics.h_advance);
+    result.set_top(glyph.metrics.top_bearing - rounding.ascender(&glyph.metrics));
+    result.set_advance(rounding.advance(&glyph.metrics));
 
     Ok(result)
 }
 
+/// Derives the fontstack name for a face: the family name followed by the style name.
+pub(crate) fn face_name(face: &freetype::Face) -> Option<String> {
+    let mut family_name = face.family_name()?;
+    if let Some(style_name) = face.style_name() {
+        family_name.push(' ');
+        family_name.push_str(&style_name);
+    }
+    Some(family_name)
+}
+
 /// Renders a glyph range for the given font face into a Mapbox-compatible fontstack.
 ///
 /// The `radius` and `cutoff` parameters are exposed in case you are working with an
@@ -38,6 +91,10 @@ pub fn render_sdf_glyph(
 /// percentage of values will be used to record the negative values (since the SDF is
 /// encoded as a vector of bytes, which have no sign). The value selected must be
 /// between 0 and 1.
+///
+/// With the `shaping` feature enabled, any Arabic presentation forms in the range which the
+/// face doesn't map are synthesized from its `init`, `medi`, `fina` and `isol` features, so
+/// renderers relying on presentation forms can display joined Arabic.
 pub fn glyph_range_for_face(
     face: &freetype::Face,
     start: u32,
@@ -46,38 +103,160 @@ pub fn glyph_range_for_face(
     radius: usize,
     cutoff: f64,
 ) -> Result<Fontstack, PbfFontError> {
-    let Some(mut family_name) = face.family_name() else {
+    let options = RenderOptions::new().with_render_settings(size, radius, cutoff);
+    glyph_range_for_face_with_options(face, start, end, &options)
+}
+
+/// Renders a glyph range for the given font face, using the given [`RenderOptions`].
+///
+/// This behaves like [`glyph_range_for_face`], but also supports fractional font sizes and
+/// resolutions, rounding metrics to the nearest pixel, trimming empty glyph borders, synthetic
+/// bold and oblique styles, baking OpenType features and language-specific glyphs into the
+/// range, and naming the resulting fontstack.
+pub fn glyph_range_for_face_with_options(
+    face: &freetype::Face,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+) -> Result<Fontstack, PbfFontError> {
+    render_glyph_range(face, start, end, options, |_, e| Err(e))
+}
+
+/// Renders a glyph range for the given font face, skipping any glyphs which fail to render.
+///
+/// This behaves like [`glyph_range_for_face`], except that an error rendering a single glyph
+/// (a broken composite glyph, for example) does not fail the entire range. Instead, the failed
+/// glyph is left out of the stack, and the error is returned alongside it together with the
+/// code point.
+///
+/// An error is still returned for problems affecting the entire face,
+/// such as a missing family name.
+pub fn glyph_range_for_face_lossy(
+    face: &freetype::Face,
+    start: u32,
+    end: u32,
+    size: usize,
+    radius: usize,
+    cutoff: f64,
+) -> Result<(Fontstack, Vec<(u32, PbfFontError)>), PbfFontError> {
+    let options = RenderOptions::new().with_render_settings(size, radius, cutoff);
+    glyph_range_for_face_lossy_with_options(face, start, end, &options)
+}
+
+/// A version of [`glyph_range_for_face_lossy`] using the given [`RenderOptions`].
+pub fn glyph_range_for_face_lossy_with_options(
+    face: &freetype::Face,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+) -> Result<(Fontstack, Vec<(u32, PbfFontError)>), PbfFontError> {
+    let mut errors = Vec::new();
+    let stack = render_glyph_range(face, start, end, options, |char_code, e| {
+        errors.push((char_code, e));
+        Ok(())
+    })?;
+
+    Ok((stack, errors))
+}
+
+/// Finds the code points in the range which should be rendered from a glyph other than the
+/// one the face maps them to (if any), returning a map from code point to glyph index.
+#[cfg(feature = "shaping")]
+fn glyph_overrides(
+    face: &freetype::Face,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+) -> HashMap<u32, u32> {
+    let mut overrides = crate::presentation_forms::synthesize_presentation_forms(face, start, end);
+    overrides.extend(crate::shaping::substitute_features(
+        face,
+        start,
+        end,
+        &options.features,
+        options.language.as_ref(),
+    ));
+    overrides
+}
+
+#[cfg(not(feature = "shaping"))]
+fn glyph_overrides(
+    _face: &freetype::Face,
+    _start: u32,
+    _end: u32,
+    _options: &RenderOptions,
+) -> HashMap<u32, u32> {
+    HashMap::new()
+}
+
+/// Renders a glyph range, passing any per-glyph errors to `on_error`. Rendering is aborted
+/// if `on_error` returns an error.
+fn render_glyph_range<F>(
+    face: &freetype::Face,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+    mut on_error: F,
+) -> Result<Fontstack, PbfFontError>
+where
+    F: FnMut(u32, PbfFontError) -> Result<(), PbfFontError>,
+{
+    let Some(family_name) = options.stack_name(face) else {
         return Err(PbfFontError::MissingFontFamilyName);
     };
-    if let Some(style_name) = face.style_name() {
-        family_name.push(' ');
-        family_name.push_str(&style_name);
-    }
+    let RenderOptions {
+        radius,
+        cutoff,
+        style,
+        rounding,
+        trim,
+        ..
+    } = *options;
 
     let mut stack = Fontstack::new();
     stack.set_name(family_name);
     stack.set_range(format!("{start}-{end}"));
 
-    // FreeType conventions: char width or height of zero means "use the same value"
-    // and setting both resolution values to zero results in the default value
-    // of 72 dpi.
-    //
-    // See https://www.freetype.org/freetype2/docs/reference/ft2-base_interface.html#ft_set_char_size
-    // and https://www.freetype.org/freetype2/docs/tutorial/step1.html for details.
-    face.set_char_size(0, (size << 6) as isize, 0, 0)?;
+    // Bitmap-only faces (such as colour emoji fonts) are rendered from the closest strike
+    // (clones share the underlying FreeType face, so this sets the size of `face` too)
+    let bitmap_scale = set_pixel_size(&mut face.clone(), options.pixel_size())?;
+    let glyph_options = GlyphRenderOptions::new()
+        .with_style(style)
+        .with_bitmap_scale(bitmap_scale)
+        .with_trim(trim);
+
+    let overrides = glyph_overrides(face, start, end, options);
 
     for char_code in start..=end {
-        match render_sdf_glyph(face, char_code, 3, radius, cutoff) {
+        let glyph_index = match overrides.get(&char_code) {
+            Some(&glyph_index) => Ok(glyph_index),
+            None => face
+                .get_char_index(char_code as usize)
+                .map(NonZeroU32::get)
+                .map_err(|_| SdfGlyphError::MissingGlyph(char_code)),
+        };
+        let result = glyph_index
+            .and_then(|glyph_index| {
+                render_sdf_from_glyph_index_with_options(
+                    face,
+                    glyph_index,
+                    3,
+                    radius,
+                    &glyph_options,
+                )
+            })
+            .map_err(PbfFontError::from)
+            .and_then(|glyph| encode_glyph(&glyph, char_code, cutoff, rounding));
+
+        match result {
             Ok(glyph) => {
                 stack.glyphs.push(glyph);
             }
-            Err(PbfFontError::SdfGlyphError(sdf_glyph_renderer::SdfGlyphError::FreeTypeError(
-                freetype::Error::InvalidGlyphIndex,
-            ))) => {
+            Err(PbfFontError::SdfGlyphError(SdfGlyphError::MissingGlyph(_))) => {
                 // Do nothing; not all glyphs will be present in a font.
             }
             Err(e) => {
-                return Err(e);
+                on_error(char_code, e)?;
             }
         }
     }
@@ -111,3 +290,307 @@ pub fn glyph_range_for_font<P: AsRef<Path>>(
 
     Ok(result)
 }
+
+/// The number of code points in each unit of work handed out by the parallel renderers.
+const CHUNK_SIZE: u32 = 64;
+
+/// A chunk of code points to render for a single face.
+struct RenderJob {
+    face_index: isize,
+    start: u32,
+    end: u32,
+}
+
+/// A queue of render jobs shared between workers.
+struct RenderQueue {
+    jobs: Vec<RenderJob>,
+    next: AtomicUsize,
+}
+
+/// The glyphs rendered by a worker for each job, keyed by the job's position in the queue,
+/// along with any per-glyph errors skipped in lossy mode.
+type WorkerResult = Result<Vec<(usize, Vec<Glyph>, Vec<(u32, PbfFontError)>)>, PbfFontError>;
+
+/// Glyphs rendered for a whole font, along with the face index and code point of every glyph
+/// which failed to render in lossy mode.
+type FontRenderResult = Result<(Glyphs, Vec<(isize, u32, PbfFontError)>), PbfFontError>;
+
+/// Splits rendering a font into jobs by face and by code point chunk.
+///
+/// Returns an empty (but named) font stack for each face, and the list of jobs
+/// in face and code point order.
+fn plan_render_jobs(
+    font_path: &Path,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+) -> Result<(Vec<Fontstack>, RenderQueue), PbfFontError> {
+    let lib = freetype::Library::init()?;
+    let num_faces = lib.new_face(font_path, 0)?.num_faces() as isize;
+
+    let mut stacks = Vec::with_capacity(num_faces as usize);
+    let mut jobs = Vec::new();
+    for face_index in 0..num_faces {
+        let face = lib.new_face(font_path, face_index)?;
+        let Some(name) = options.stack_name(&face) else {
+            return Err(PbfFontError::MissingFontFamilyName);
+        };
+
+        let mut stack = Fontstack::new();
+        stack.set_name(name);
+        stack.set_range(format!("{start}-{end}"));
+        stacks.push(stack);
+
+        let mut chunk_start = start;
+        while chunk_start <= end {
+            let chunk_end = chunk_start.saturating_add(CHUNK_SIZE - 1).min(end);
+            jobs.push(RenderJob {
+                face_index,
+                start: chunk_start,
+                end: chunk_end,
+            });
+            if chunk_end == u32::MAX {
+                break;
+            }
+            chunk_start = chunk_end + 1;
+        }
+    }
+
+    Ok((
+        stacks,
+        RenderQueue {
+            jobs,
+            next: AtomicUsize::new(0),
+        },
+    ))
+}
+
+/// Renders jobs from the queue until it is exhausted, using a single FreeType library
+/// (and one face per face index) for the lifetime of the worker.
+///
+/// In `lossy` mode, glyphs which fail to render are skipped as in
+/// [`glyph_range_for_face_lossy`]; otherwise the first error stops all workers.
+fn render_worker(
+    font_path: &Path,
+    queue: &RenderQueue,
+    options: &RenderOptions,
+    lossy: bool,
+) -> WorkerResult {
+    let lib = freetype::Library::init()?;
+    // Note: faces must be dropped before the library, which is guaranteed by declaring them later.
+    let mut faces: HashMap<isize, freetype::Face> = HashMap::new();
+    let mut results = Vec::new();
+
+    loop {
+        let job_index = queue.next.fetch_add(1, Ordering::Relaxed);
+        let Some(job) = queue.jobs.get(job_index) else {
+            break;
+        };
+
+        let face = match faces.entry(job.face_index) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(lib.new_face(font_path, job.face_index)?),
+        };
+
+        let result = if lossy {
+            glyph_range_for_face_lossy_with_options(face, job.start, job.end, options)
+        } else {
+            glyph_range_for_face_with_options(face, job.start, job.end, options)
+                .map(|stack| (stack, Vec::new()))
+        };
+        match result {
+            Ok((stack, errors)) => results.push((job_index, stack.glyphs, errors)),
+            Err(e) => {
+                // Stop the other workers from picking up any more jobs
+                queue.next.store(queue.jobs.len(), Ordering::Relaxed);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Reassembles the output of the render workers into a glyphs message.
+fn collect_render_results(
+    mut stacks: Vec<Fontstack>,
+    queue: &RenderQueue,
+    worker_results: Vec<WorkerResult>,
+) -> FontRenderResult {
+    let mut job_results = Vec::with_capacity(queue.jobs.len());
+    for worker_result in worker_results {
+        job_results.extend(worker_result?);
+    }
+    job_results.sort_unstable_by_key(|(job_index, _, _)| *job_index);
+
+    let mut errors = Vec::new();
+    for (job_index, glyphs, job_errors) in job_results {
+        let face_index = queue.jobs[job_index].face_index;
+        stacks[face_index as usize].glyphs.extend(glyphs);
+        errors.extend(
+            job_errors
+                .into_iter()
+                .map(|(char_code, e)| (face_index, char_code, e)),
+        );
+    }
+
+    let mut result = Glyphs::new();
+    result.stacks = stacks;
+    Ok((result, errors))
+}
+
+fn default_worker_count() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// A parallel version of [`glyph_range_for_font`].
+///
+/// The work is split by face and by chunks of code points, and spread over `num_threads`
+/// worker threads, each of which uses its own FreeType library. If `num_threads` is zero,
+/// the available parallelism of the system is used.
+///
+/// The result is identical to that of [`glyph_range_for_font`].
+pub fn glyph_range_for_font_parallel<P: AsRef<Path>>(
+    font_path: P,
+    start: u32,
+    end: u32,
+    size: usize,
+    radius: usize,
+    cutoff: f64,
+    num_threads: usize,
+) -> Result<Glyphs, PbfFontError> {
+    let options = RenderOptions::new().with_render_settings(size, radius, cutoff);
+    glyph_range_for_font_parallel_with_options(font_path, start, end, &options, num_threads)
+}
+
+/// A version of [`glyph_range_for_font_parallel`] using the given [`RenderOptions`].
+///
+/// Each face is rendered as if by [`glyph_range_for_face_with_options`].
+pub fn glyph_range_for_font_parallel_with_options<P: AsRef<Path>>(
+    font_path: P,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+    num_threads: usize,
+) -> Result<Glyphs, PbfFontError> {
+    render_font_parallel(font_path.as_ref(), start, end, options, num_threads, false)
+        .map(|(glyphs, _)| glyphs)
+}
+
+/// A lossy version of [`glyph_range_for_font_parallel_with_options`].
+///
+/// Each face is rendered as if by [`glyph_range_for_face_lossy_with_options`]. The errors
+/// for glyphs which failed to render are returned with their face index and code point.
+pub fn glyph_range_for_font_parallel_lossy_with_options<P: AsRef<Path>>(
+    font_path: P,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+    num_threads: usize,
+) -> FontRenderResult {
+    render_font_parallel(font_path.as_ref(), start, end, options, num_threads, true)
+}
+
+fn render_font_parallel(
+    font_path: &Path,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+    num_threads: usize,
+    lossy: bool,
+) -> FontRenderResult {
+    let num_threads = if num_threads == 0 {
+        default_worker_count()
+    } else {
+        num_threads
+    };
+
+    let (stacks, queue) = plan_render_jobs(font_path, start, end, options)?;
+    let worker_results = thread::scope(|scope| {
+        let handles: Vec<_> = (0..num_threads.min(queue.jobs.len()))
+            .map(|_| scope.spawn(|| render_worker(font_path, &queue, options, lossy)))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Render worker panicked"))
+            .collect()
+    });
+
+    collect_render_results(stacks, &queue, worker_results)
+}
+
+/// An async version of [`glyph_range_for_font`].
+///
+/// Like [`glyph_range_for_font_parallel`], the work is split by face and by chunks of code
+/// points, but the workers run on tokio's blocking thread pool, one per available CPU.
+///
+/// The result is identical to that of [`glyph_range_for_font`].
+pub async fn glyph_range_for_font_async<P: AsRef<Path>>(
+    font_path: P,
+    start: u32,
+    end: u32,
+    size: usize,
+    radius: usize,
+    cutoff: f64,
+) -> Result<Glyphs, PbfFontError> {
+    let options = RenderOptions::new().with_render_settings(size, radius, cutoff);
+    glyph_range_for_font_async_with_options(font_path, start, end, &options).await
+}
+
+/// A version of [`glyph_range_for_font_async`] using the given [`RenderOptions`].
+///
+/// Each face is rendered as if by [`glyph_range_for_face_with_options`].
+pub async fn glyph_range_for_font_async_with_options<P: AsRef<Path>>(
+    font_path: P,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+) -> Result<Glyphs, PbfFontError> {
+    render_font_async(font_path.as_ref(), start, end, options, false)
+        .await
+        .map(|(glyphs, _)| glyphs)
+}
+
+/// A lossy version of [`glyph_range_for_font_async_with_options`].
+///
+/// See [`glyph_range_for_font_parallel_lossy_with_options`] for how errors are returned.
+pub async fn glyph_range_for_font_async_lossy_with_options<P: AsRef<Path>>(
+    font_path: P,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+) -> FontRenderResult {
+    render_font_async(font_path.as_ref(), start, end, options, true).await
+}
+
+async fn render_font_async(
+    font_path: &Path,
+    start: u32,
+    end: u32,
+    options: &RenderOptions,
+    lossy: bool,
+) -> FontRenderResult {
+    let font_path = Arc::new(font_path.to_path_buf());
+    let options = Arc::new(options.clone());
+
+    let plan_path = font_path.clone();
+    let plan_options = options.clone();
+    let (stacks, queue) =
+        spawn_blocking(move || plan_render_jobs(&plan_path, start, end, &plan_options)).await??;
+    let queue = Arc::new(queue);
+
+    let workers = (0..default_worker_count().min(queue.jobs.len())).map(|_| {
+        let font_path = font_path.clone();
+        let queue = queue.clone();
+        let options = options.clone();
+        spawn_blocking(move || render_worker(&font_path, &queue, &options, lossy))
+    });
+
+    let mut worker_results = Vec::new();
+    for worker_result in join_all(workers).await {
+        worker_results.push(worker_result?);
+    }
+
+    collect_render_results(stacks, &queue, worker_results)
+}
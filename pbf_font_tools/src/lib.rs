@@ -6,6 +6,14 @@
 //! Generating glyphs from a TrueType/OpenType font (a la [node-fontnik](https://github.com/mapbox/node-fontnik))
 //! is planned for a future release.
 //!
+//! With the optional `axum` feature enabled, the [`service`] module provides a router for
+//! serving combined glyph ranges over HTTP.
+//!
+//! With the optional `shaping` feature enabled, [`shape_labels`] pre-shapes label strings for
+//! complex scripts, which renderers cannot shape on their own. It also lets
+//! [`glyph_range_for_face`] fill in the Arabic presentation forms from the font's joining
+//! features, for fonts which don't map them directly.
+//!
 //! ## References
 //!   * [glyph-pbf-composite](https://github.com/mapbox/glyph-pbf-composite)
 //!   * [tileserver-gl](https://github.com/klokantech/tileserver-gl/blob/master/src/utils.js)
@@ -14,16 +22,36 @@ mod error;
 mod proto;
 mod tools;
 
+#[cfg(feature = "axum")]
+pub mod service;
+
+#[cfg(feature = "freetype")]
+mod fallback;
 #[cfg(feature = "freetype")]
 mod ft_generate;
+#[cfg(feature = "freetype")]
+mod options;
+#[cfg(feature = "shaping")]
+mod presentation_forms;
+#[cfg(feature = "shaping")]
+mod shaping;
 pub use proto::glyphs::{Fontstack, Glyph, Glyphs};
 // Re-export protobuf lib
 pub use protobuf;
 // Re-export freetype lib
 #[cfg(feature = "freetype")]
 pub use sdf_glyph_renderer::freetype;
+// Re-export rustybuzz lib
+#[cfg(feature = "shaping")]
+pub use rustybuzz;
 
 pub use crate::error::PbfFontError;
 #[cfg(feature = "freetype")]
+pub use crate::fallback::*;
+#[cfg(feature = "freetype")]
 pub use crate::ft_generate::*;
+#[cfg(feature = "freetype")]
+pub use crate::options::*;
+#[cfg(feature = "shaping")]
+pub use crate::shaping::*;
 pub use crate::tools::*;
@@ -0,0 +1,212 @@
+use sdf_glyph_renderer::{GlyphMetrics, SyntheticStyle};
+
+use crate::freetype;
+use crate::ft_generate::face_name;
+
+/// How fractional glyph metrics are rounded when encoding them into a [`Glyph`](crate::Glyph),
+/// whose fields are whole pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// Uses FreeType's integer metrics: the advance is rounded down, and the ascender (which
+    /// offsets the top bearing) is rounded up. This matches node-fontnik and earlier releases.
+    #[default]
+    Compatible,
+
+    /// Rounds the advance and ascender to the nearest pixel. This halves the worst case error
+    /// per glyph, and the errors of a line of text tend to cancel out rather than accumulate.
+    Nearest,
+}
+
+impl Rounding {
+    /// Returns the advance of a glyph in whole pixels.
+    #[must_use]
+    pub fn advance(self, metrics: &GlyphMetrics) -> u32 {
+        match self {
+            Rounding::Compatible => metrics.h_advance,
+            Rounding::Nearest => metrics.precise_h_advance().round() as u32,
+        }
+    }
+
+    /// Returns the ascender of a glyph's face in whole pixels.
+    #[must_use]
+    pub fn ascender(self, metrics: &GlyphMetrics) -> i32 {
+        match self {
+            Rounding::Compatible => metrics.ascender,
+            Rounding::Nearest => metrics.precise_ascender().round() as i32,
+        }
+    }
+}
+
+/// Settings for rendering glyph ranges with [`glyph_range_for_face_with_options`].
+///
+/// The defaults match the settings used by `build_pbf_glyphs`
+/// (and the plain [`glyph_range_for_face`]).
+///
+/// [`glyph_range_for_face`]: crate::glyph_range_for_face
+/// [`glyph_range_for_face_with_options`]: crate::glyph_range_for_face_with_options
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderOptions {
+    pub(crate) size: f64,
+    pub(crate) dpi: u32,
+    pub(crate) radius: usize,
+    pub(crate) cutoff: f64,
+    pub(crate) name: Option<String>,
+    pub(crate) style: SyntheticStyle,
+    pub(crate) rounding: Rounding,
+    pub(crate) trim: bool,
+    #[cfg(feature = "shaping")]
+    pub(crate) features: Vec<rustybuzz::Feature>,
+    #[cfg(feature = "shaping")]
+    pub(crate) language: Option<rustybuzz::Language>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            size: 24.0,
+            dpi: 72,
+            radius: 8,
+            cutoff: 0.25,
+            name: None,
+            style: SyntheticStyle::default(),
+            rounding: Rounding::default(),
+            trim: false,
+            #[cfg(feature = "shaping")]
+            features: Vec::new(),
+            #[cfg(feature = "shaping")]
+            language: None,
+        }
+    }
+}
+
+impl RenderOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the font size, `radius` and `cutoff` used for rendering.
+    /// See [`glyph_range_for_face`](crate::glyph_range_for_face) for details.
+    #[must_use]
+    pub fn with_render_settings(mut self, size: usize, radius: usize, cutoff: f64) -> Self {
+        self.size = size as f64;
+        self.radius = radius;
+        self.cutoff = cutoff;
+        self
+    }
+
+    /// Sets the font size in points, which may be fractional.
+    #[must_use]
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the resolution the font size is rendered at, in dots per inch. The default of 72
+    /// makes a point the same as a pixel.
+    #[must_use]
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// Sets how fractional metrics are rounded when encoding glyphs. See [`Rounding`].
+    #[must_use]
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Trims empty rows and columns from the edges of glyphs before rendering them, adjusting
+    /// the bearings to match. Blank glyphs such as spaces are stored without a bitmap.
+    ///
+    /// This makes ranges smaller and glyph atlases tighter, but the glyphs no longer match those
+    /// rendered by node-fontnik byte for byte.
+    #[must_use]
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Sets the name of the rendered fontstack, instead of deriving it from the face.
+    ///
+    /// Glyphs rendered with different features or languages should be given a name of their
+    /// own, since they are no longer interchangeable with the default glyphs.
+    #[must_use]
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Emboldens glyphs by `strength`, a fraction of the em size, for faces without a real bold
+    /// cut. Advances grow by the same amount. FreeType's own synthetic bold uses 1/24.
+    #[must_use]
+    pub fn with_embolden(mut self, strength: f64) -> Self {
+        self.style.embolden = strength;
+        self
+    }
+
+    /// Slants glyphs by a horizontal `shear` (the tangent of the slant angle), for faces without
+    /// a real italic cut. FreeType's own synthetic oblique uses about 0.21 (12 degrees).
+    #[must_use]
+    pub fn with_oblique(mut self, shear: f64) -> Self {
+        self.style.oblique = shear;
+        self
+    }
+
+    /// Returns the name of the fontstack rendered from `face` with these options.
+    ///
+    /// Unless a name has been set, this is derived from the face like in
+    /// [`glyph_range_for_face`](crate::glyph_range_for_face), with any synthetic style
+    /// appended. A "Regular" style is replaced, so emboldening "Foo Regular" produces
+    /// "Foo Bold (synthetic)", and emboldening "Foo Light" produces "Foo Light Bold (synthetic)".
+    #[must_use]
+    pub fn stack_name(&self, face: &freetype::Face) -> Option<String> {
+        if let Some(name) = &self.name {
+            return Some(name.clone());
+        }
+        let Some(synthetic_style) = self.style.style_name() else {
+            return face_name(face);
+        };
+
+        let mut name = face.family_name()?;
+        if let Some(style_name) = face
+            .style_name()
+            .filter(|style_name| style_name != "Regular")
+        {
+            name.push(' ');
+            name.push_str(&style_name);
+        }
+        name.push_str(&format!(" {synthetic_style} (synthetic)"));
+        Some(name)
+    }
+
+    /// Sets the OpenType features to bake into the rendered glyphs, such as `tnum` (tabular
+    /// numbers) or `smcp` (small caps).
+    ///
+    /// Only substitutions which replace a code point's glyph with a single other glyph
+    /// take effect, since each code point is rendered on its own.
+    #[cfg(feature = "shaping")]
+    #[must_use]
+    pub fn with_features<I: IntoIterator<Item = rustybuzz::Feature>>(
+        mut self,
+        features: I,
+    ) -> Self {
+        self.features = features.into_iter().collect();
+        self
+    }
+
+    /// Sets the language used to select language-specific glyphs (the `locl` feature), such
+    /// as the regional variants of a Pan-CJK font.
+    #[cfg(feature = "shaping")]
+    #[must_use]
+    pub fn with_language(mut self, language: rustybuzz::Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// The font size in pixels per em.
+    pub(crate) fn pixel_size(&self) -> f64 {
+        self.size * f64::from(self.dpi) / 72.0
+    }
+}
@@ -0,0 +1,843 @@
+//! Synthesis of Arabic presentation forms from a font's GSUB joining features.
+//!
+//! Renderers without a shaper (together with an RTL plugin such as MapLibre's) rely on the
+//! Arabic Presentation Forms-A and -B blocks to display joined Arabic. Many modern fonts
+//! (Noto Naskh Arabic, for example) don't map those code points at all, and only provide
+//! the joining forms through the `init`, `medi`, `fina` and `isol` GSUB features.
+//!
+//! For each presentation form the font doesn't map, we shape its base letters in the
+//! matching joining context and use the resulting glyph, if shaping produces exactly one.
+//! Only the joining features are applied, so that contextual alternates, optional ligatures
+//! and the like don't end up in the synthesized forms. The exception is the ligature forms
+//! (such as LAM WITH ALEF), which fonts provide through their `rlig` and `liga` features.
+
+use std::collections::HashMap;
+
+use crate::freetype;
+use crate::shaping::load_font_data;
+use JoiningForm::{Final, Initial, Isolated, Medial};
+
+/// The code point ranges of the Arabic Presentation Forms-A and -B blocks.
+const PRESENTATION_FORM_RANGES: [(u32, u32); 2] = [(0xFB50, 0xFDFF), (0xFE70, 0xFEFF)];
+
+/// The tatweel (kashida), a dual-joining letter used to give the base letters their context.
+const TATWEEL: char = '\u{0640}';
+
+/// The GSUB features the shaper applies to Arabic by default, other than the joining features
+/// (`isol`, `fina`, `medi` and `init`, and their Syriac variants). None of these are applied
+/// when synthesizing presentation forms, except for the ligatures in ligature forms.
+/// Positioning features are left alone, since they don't change which glyph is used.
+const NON_JOINING_FEATURES: [&[u8; 4]; 11] = [
+    b"ccmp", b"locl", b"stch", b"rlig", b"calt", b"rclt", b"liga", b"clig", b"mset", b"rtla",
+    b"rtlm",
+];
+
+/// The features which form the ligatures in ligature presentation forms.
+const LIGATURE_FEATURES: [&[u8; 4]; 2] = [b"rlig", b"liga"];
+
+/// The joining form of a presentation form, as given by its compatibility decomposition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum JoiningForm {
+    Isolated,
+    Initial,
+    Medial,
+    Final,
+}
+
+/// Shapes `letters` in the context required by `form`, returning the glyph index if the
+/// result is a single glyph.
+fn shape_form(face: &rustybuzz::Face, form: JoiningForm, letters: &[char]) -> Option<u32> {
+    let mut text = String::new();
+    if matches!(form, JoiningForm::Medial | JoiningForm::Final) {
+        text.push(TATWEEL);
+    }
+    let letters_start = text.len();
+    text.extend(letters);
+    let letters_end = text.len();
+    if matches!(form, JoiningForm::Initial | JoiningForm::Medial) {
+        text.push(TATWEEL);
+    }
+
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    buffer.push_str(&text);
+    buffer.guess_segment_properties();
+    let features: Vec<_> = NON_JOINING_FEATURES
+        .iter()
+        .filter(|&&tag| letters.len() == 1 || !LIGATURE_FEATURES.contains(&tag))
+        .map(|tag| rustybuzz::Feature::new(rustybuzz::ttf_parser::Tag::from_bytes(tag), 0, ..))
+        .collect();
+    let output = rustybuzz::shape(face, &features, buffer);
+
+    // Discard the glyphs for the context
+    let mut glyphs = output
+        .glyph_infos()
+        .iter()
+        .filter(|info| (letters_start..letters_end).contains(&(info.cluster as usize)));
+    match (glyphs.next(), glyphs.next()) {
+        (Some(info), None) if info.glyph_id != 0 => Some(info.glyph_id),
+        _ => None,
+    }
+}
+
+/// Finds glyphs for the presentation forms between `start` and `end` (inclusive) which
+/// the face doesn't map directly, returning a map from code point to glyph index.
+///
+/// The map is empty for faces without Arabic letters, or whose font data can't be read.
+pub(crate) fn synthesize_presentation_forms(
+    face: &freetype::Face,
+    start: u32,
+    end: u32,
+) -> HashMap<u32, u32> {
+    let mut glyphs = HashMap::new();
+    let overlaps = PRESENTATION_FORM_RANGES
+        .iter()
+        .any(|&(range_start, range_end)| start <= range_end && end >= range_start);
+    // Avoid loading the font data for faces which can't have any joining forms
+    if !overlaps || face.get_char_index('\u{0627}' as usize).is_err() {
+        return glyphs;
+    }
+
+    let Some(font_data) = load_font_data(face) else {
+        return glyphs;
+    };
+    let face_index = (face.raw().face_index & 0xFFFF) as u32;
+    let Some(shaper_face) = rustybuzz::Face::from_slice(&font_data, face_index) else {
+        return glyphs;
+    };
+
+    for &(code_point, form, letters) in PRESENTATION_FORMS {
+        if code_point < start || code_point > end {
+            continue;
+        }
+        if face.get_char_index(code_point as usize).is_ok() {
+            continue;
+        }
+        if let Some(glyph_index) = shape_form(&shaper_face, form, letters) {
+            glyphs.insert(code_point, glyph_index);
+        }
+    }
+
+    glyphs
+}
+
+/// The presentation forms and their compatibility decompositions, from the Unicode
+/// Character Database (version 14.0). Forms which decompose to a space followed by a mark
+/// are omitted, since they never shape to a single glyph.
+#[rustfmt::skip]
+const PRESENTATION_FORMS: &[(u32, JoiningForm, &[char])] = &[
+    (0xFB50, Isolated, &['\u{0671}']),
+    (0xFB51, Final, &['\u{0671}']),
+    (0xFB52, Isolated, &['\u{067B}']),
+    (0xFB53, Final, &['\u{067B}']),
+    (0xFB54, Initial, &['\u{067B}']),
+    (0xFB55, Medial, &['\u{067B}']),
+    (0xFB56, Isolated, &['\u{067E}']),
+    (0xFB57, Final, &['\u{067E}']),
+    (0xFB58, Initial, &['\u{067E}']),
+    (0xFB59, Medial, &['\u{067E}']),
+    (0xFB5A, Isolated, &['\u{0680}']),
+    (0xFB5B, Final, &['\u{0680}']),
+    (0xFB5C, Initial, &['\u{0680}']),
+    (0xFB5D, Medial, &['\u{0680}']),
+    (0xFB5E, Isolated, &['\u{067A}']),
+    (0xFB5F, Final, &['\u{067A}']),
+    (0xFB60, Initial, &['\u{067A}']),
+    (0xFB61, Medial, &['\u{067A}']),
+    (0xFB62, Isolated, &['\u{067F}']),
+    (0xFB63, Final, &['\u{067F}']),
+    (0xFB64, Initial, &['\u{067F}']),
+    (0xFB65, Medial, &['\u{067F}']),
+    (0xFB66, Isolated, &['\u{0679}']),
+    (0xFB67, Final, &['\u{0679}']),
+    (0xFB68, Initial, &['\u{0679}']),
+    (0xFB69, Medial, &['\u{0679}']),
+    (0xFB6A, Isolated, &['\u{06A4}']),
+    (0xFB6B, Final, &['\u{06A4}']),
+    (0xFB6C, Initial, &['\u{06A4}']),
+    (0xFB6D, Medial, &['\u{06A4}']),
+    (0xFB6E, Isolated, &['\u{06A6}']),
+    (0xFB6F, Final, &['\u{06A6}']),
+    (0xFB70, Initial, &['\u{06A6}']),
+    (0xFB71, Medial, &['\u{06A6}']),
+    (0xFB72, Isolated, &['\u{0684}']),
+    (0xFB73, Final, &['\u{0684}']),
+    (0xFB74, Initial, &['\u{0684}']),
+    (0xFB75, Medial, &['\u{0684}']),
+    (0xFB76, Isolated, &['\u{0683}']),
+    (0xFB77, Final, &['\u{0683}']),
+    (0xFB78, Initial, &['\u{0683}']),
+    (0xFB79, Medial, &['\u{0683}']),
+    (0xFB7A, Isolated, &['\u{0686}']),
+    (0xFB7B, Final, &['\u{0686}']),
+    (0xFB7C, Initial, &['\u{0686}']),
+    (0xFB7D, Medial, &['\u{0686}']),
+    (0xFB7E, Isolated, &['\u{0687}']),
+    (0xFB7F, Final, &['\u{0687}']),
+    (0xFB80, Initial, &['\u{0687}']),
+    (0xFB81, Medial, &['\u{0687}']),
+    (0xFB82, Isolated, &['\u{068D}']),
+    (0xFB83, Final, &['\u{068D}']),
+    (0xFB84, Isolated, &['\u{068C}']),
+    (0xFB85, Final, &['\u{068C}']),
+    (0xFB86, Isolated, &['\u{068E}']),
+    (0xFB87, Final, &['\u{068E}']),
+    (0xFB88, Isolated, &['\u{0688}']),
+    (0xFB89, Final, &['\u{0688}']),
+    (0xFB8A, Isolated, &['\u{0698}']),
+    (0xFB8B, Final, &['\u{0698}']),
+    (0xFB8C, Isolated, &['\u{0691}']),
+    (0xFB8D, Final, &['\u{0691}']),
+    (0xFB8E, Isolated, &['\u{06A9}']),
+    (0xFB8F, Final, &['\u{06A9}']),
+    (0xFB90, Initial, &['\u{06A9}']),
+    (0xFB91, Medial, &['\u{06A9}']),
+    (0xFB92, Isolated, &['\u{06AF}']),
+    (0xFB93, Final, &['\u{06AF}']),
+    (0xFB94, Initial, &['\u{06AF}']),
+    (0xFB95, Medial, &['\u{06AF}']),
+    (0xFB96, Isolated, &['\u{06B3}']),
+    (0xFB97, Final, &['\u{06B3}']),
+    (0xFB98, Initial, &['\u{06B3}']),
+    (0xFB99, Medial, &['\u{06B3}']),
+    (0xFB9A, Isolated, &['\u{06B1}']),
+    (0xFB9B, Final, &['\u{06B1}']),
+    (0xFB9C, Initial, &['\u{06B1}']),
+    (0xFB9D, Medial, &['\u{06B1}']),
+    (0xFB9E, Isolated, &['\u{06BA}']),
+    (0xFB9F, Final, &['\u{06BA}']),
+    (0xFBA0, Isolated, &['\u{06BB}']),
+    (0xFBA1, Final, &['\u{06BB}']),
+    (0xFBA2, Initial, &['\u{06BB}']),
+    (0xFBA3, Medial, &['\u{06BB}']),
+    (0xFBA4, Isolated, &['\u{06C0}']),
+    (0xFBA5, Final, &['\u{06C0}']),
+    (0xFBA6, Isolated, &['\u{06C1}']),
+    (0xFBA7, Final, &['\u{06C1}']),
+    (0xFBA8, Initial, &['\u{06C1}']),
+    (0xFBA9, Medial, &['\u{06C1}']),
+    (0xFBAA, Isolated, &['\u{06BE}']),
+    (0xFBAB, Final, &['\u{06BE}']),
+    (0xFBAC, Initial, &['\u{06BE}']),
+    (0xFBAD, Medial, &['\u{06BE}']),
+    (0xFBAE, Isolated, &['\u{06D2}']),
+    (0xFBAF, Final, &['\u{06D2}']),
+    (0xFBB0, Isolated, &['\u{06D3}']),
+    (0xFBB1, Final, &['\u{06D3}']),
+    (0xFBD3, Isolated, &['\u{06AD}']),
+    (0xFBD4, Final, &['\u{06AD}']),
+    (0xFBD5, Initial, &['\u{06AD}']),
+    (0xFBD6, Medial, &['\u{06AD}']),
+    (0xFBD7, Isolated, &['\u{06C7}']),
+    (0xFBD8, Final, &['\u{06C7}']),
+    (0xFBD9, Isolated, &['\u{06C6}']),
+    (0xFBDA, Final, &['\u{06C6}']),
+    (0xFBDB, Isolated, &['\u{06C8}']),
+    (0xFBDC, Final, &['\u{06C8}']),
+    (0xFBDD, Isolated, &['\u{0677}']),
+    (0xFBDE, Isolated, &['\u{06CB}']),
+    (0xFBDF, Final, &['\u{06CB}']),
+    (0xFBE0, Isolated, &['\u{06C5}']),
+    (0xFBE1, Final, &['\u{06C5}']),
+    (0xFBE2, Isolated, &['\u{06C9}']),
+    (0xFBE3, Final, &['\u{06C9}']),
+    (0xFBE4, Isolated, &['\u{06D0}']),
+    (0xFBE5, Final, &['\u{06D0}']),
+    (0xFBE6, Initial, &['\u{06D0}']),
+    (0xFBE7, Medial, &['\u{06D0}']),
+    (0xFBE8, Initial, &['\u{0649}']),
+    (0xFBE9, Medial, &['\u{0649}']),
+    (0xFBEA, Isolated, &['\u{0626}', '\u{0627}']),
+    (0xFBEB, Final, &['\u{0626}', '\u{0627}']),
+    (0xFBEC, Isolated, &['\u{0626}', '\u{06D5}']),
+    (0xFBED, Final, &['\u{0626}', '\u{06D5}']),
+    (0xFBEE, Isolated, &['\u{0626}', '\u{0648}']),
+    (0xFBEF, Final, &['\u{0626}', '\u{0648}']),
+    (0xFBF0, Isolated, &['\u{0626}', '\u{06C7}']),
+    (0xFBF1, Final, &['\u{0626}', '\u{06C7}']),
+    (0xFBF2, Isolated, &['\u{0626}', '\u{06C6}']),
+    (0xFBF3, Final, &['\u{0626}', '\u{06C6}']),
+    (0xFBF4, Isolated, &['\u{0626}', '\u{06C8}']),
+    (0xFBF5, Final, &['\u{0626}', '\u{06C8}']),
+    (0xFBF6, Isolated, &['\u{0626}', '\u{06D0}']),
+    (0xFBF7, Final, &['\u{0626}', '\u{06D0}']),
+    (0xFBF8, Initial, &['\u{0626}', '\u{06D0}']),
+    (0xFBF9, Isolated, &['\u{0626}', '\u{0649}']),
+    (0xFBFA, Final, &['\u{0626}', '\u{0649}']),
+    (0xFBFB, Initial, &['\u{0626}', '\u{0649}']),
+    (0xFBFC, Isolated, &['\u{06CC}']),
+    (0xFBFD, Final, &['\u{06CC}']),
+    (0xFBFE, Initial, &['\u{06CC}']),
+    (0xFBFF, Medial, &['\u{06CC}']),
+    (0xFC00, Isolated, &['\u{0626}', '\u{062C}']),
+    (0xFC01, Isolated, &['\u{0626}', '\u{062D}']),
+    (0xFC02, Isolated, &['\u{0626}', '\u{0645}']),
+    (0xFC03, Isolated, &['\u{0626}', '\u{0649}']),
+    (0xFC04, Isolated, &['\u{0626}', '\u{064A}']),
+    (0xFC05, Isolated, &['\u{0628}', '\u{062C}']),
+    (0xFC06, Isolated, &['\u{0628}', '\u{062D}']),
+    (0xFC07, Isolated, &['\u{0628}', '\u{062E}']),
+    (0xFC08, Isolated, &['\u{0628}', '\u{0645}']),
+    (0xFC09, Isolated, &['\u{0628}', '\u{0649}']),
+    (0xFC0A, Isolated, &['\u{0628}', '\u{064A}']),
+    (0xFC0B, Isolated, &['\u{062A}', '\u{062C}']),
+    (0xFC0C, Isolated, &['\u{062A}', '\u{062D}']),
+    (0xFC0D, Isolated, &['\u{062A}', '\u{062E}']),
+    (0xFC0E, Isolated, &['\u{062A}', '\u{0645}']),
+    (0xFC0F, Isolated, &['\u{062A}', '\u{0649}']),
+    (0xFC10, Isolated, &['\u{062A}', '\u{064A}']),
+    (0xFC11, Isolated, &['\u{062B}', '\u{062C}']),
+    (0xFC12, Isolated, &['\u{062B}', '\u{0645}']),
+    (0xFC13, Isolated, &['\u{062B}', '\u{0649}']),
+    (0xFC14, Isolated, &['\u{062B}', '\u{064A}']),
+    (0xFC15, Isolated, &['\u{062C}', '\u{062D}']),
+    (0xFC16, Isolated, &['\u{062C}', '\u{0645}']),
+    (0xFC17, Isolated, &['\u{062D}', '\u{062C}']),
+    (0xFC18, Isolated, &['\u{062D}', '\u{0645}']),
+    (0xFC19, Isolated, &['\u{062E}', '\u{062C}']),
+    (0xFC1A, Isolated, &['\u{062E}', '\u{062D}']),
+    (0xFC1B, Isolated, &['\u{062E}', '\u{0645}']),
+    (0xFC1C, Isolated, &['\u{0633}', '\u{062C}']),
+    (0xFC1D, Isolated, &['\u{0633}', '\u{062D}']),
+    (0xFC1E, Isolated, &['\u{0633}', '\u{062E}']),
+    (0xFC1F, Isolated, &['\u{0633}', '\u{0645}']),
+    (0xFC20, Isolated, &['\u{0635}', '\u{062D}']),
+    (0xFC21, Isolated, &['\u{0635}', '\u{0645}']),
+    (0xFC22, Isolated, &['\u{0636}', '\u{062C}']),
+    (0xFC23, Isolated, &['\u{0636}', '\u{062D}']),
+    (0xFC24, Isolated, &['\u{0636}', '\u{062E}']),
+    (0xFC25, Isolated, &['\u{0636}', '\u{0645}']),
+    (0xFC26, Isolated, &['\u{0637}', '\u{062D}']),
+    (0xFC27, Isolated, &['\u{0637}', '\u{0645}']),
+    (0xFC28, Isolated, &['\u{0638}', '\u{0645}']),
+    (0xFC29, Isolated, &['\u{0639}', '\u{062C}']),
+    (0xFC2A, Isolated, &['\u{0639}', '\u{0645}']),
+    (0xFC2B, Isolated, &['\u{063A}', '\u{062C}']),
+    (0xFC2C, Isolated, &['\u{063A}', '\u{0645}']),
+    (0xFC2D, Isolated, &['\u{0641}', '\u{062C}']),
+    (0xFC2E, Isolated, &['\u{0641}', '\u{062D}']),
+    (0xFC2F, Isolated, &['\u{0641}', '\u{062E}']),
+    (0xFC30, Isolated, &['\u{0641}', '\u{0645}']),
+    (0xFC31, Isolated, &['\u{0641}', '\u{0649}']),
+    (0xFC32, Isolated, &['\u{0641}', '\u{064A}']),
+    (0xFC33, Isolated, &['\u{0642}', '\u{062D}']),
+    (0xFC34, Isolated, &['\u{0642}', '\u{0645}']),
+    (0xFC35, Isolated, &['\u{0642}', '\u{0649}']),
+    (0xFC36, Isolated, &['\u{0642}', '\u{064A}']),
+    (0xFC37, Isolated, &['\u{0643}', '\u{0627}']),
+    (0xFC38, Isolated, &['\u{0643}', '\u{062C}']),
+    (0xFC39, Isolated, &['\u{0643}', '\u{062D}']),
+    (0xFC3A, Isolated, &['\u{0643}', '\u{062E}']),
+    (0xFC3B, Isolated, &['\u{0643}', '\u{0644}']),
+    (0xFC3C, Isolated, &['\u{0643}', '\u{0645}']),
+    (0xFC3D, Isolated, &['\u{0643}', '\u{0649}']),
+    (0xFC3E, Isolated, &['\u{0643}', '\u{064A}']),
+    (0xFC3F, Isolated, &['\u{0644}', '\u{062C}']),
+    (0xFC40, Isolated, &['\u{0644}', '\u{062D}']),
+    (0xFC41, Isolated, &['\u{0644}', '\u{062E}']),
+    (0xFC42, Isolated, &['\u{0644}', '\u{0645}']),
+    (0xFC43, Isolated, &['\u{0644}', '\u{0649}']),
+    (0xFC44, Isolated, &['\u{0644}', '\u{064A}']),
+    (0xFC45, Isolated, &['\u{0645}', '\u{062C}']),
+    (0xFC46, Isolated, &['\u{0645}', '\u{062D}']),
+    (0xFC47, Isolated, &['\u{0645}', '\u{062E}']),
+    (0xFC48, Isolated, &['\u{0645}', '\u{0645}']),
+    (0xFC49, Isolated, &['\u{0645}', '\u{0649}']),
+    (0xFC4A, Isolated, &['\u{0645}', '\u{064A}']),
+    (0xFC4B, Isolated, &['\u{0646}', '\u{062C}']),
+    (0xFC4C, Isolated, &['\u{0646}', '\u{062D}']),
+    (0xFC4D, Isolated, &['\u{0646}', '\u{062E}']),
+    (0xFC4E, Isolated, &['\u{0646}', '\u{0645}']),
+    (0xFC4F, Isolated, &['\u{0646}', '\u{0649}']),
+    (0xFC50, Isolated, &['\u{0646}', '\u{064A}']),
+    (0xFC51, Isolated, &['\u{0647}', '\u{062C}']),
+    (0xFC52, Isolated, &['\u{0647}', '\u{0645}']),
+    (0xFC53, Isolated, &['\u{0647}', '\u{0649}']),
+    (0xFC54, Isolated, &['\u{0647}', '\u{064A}']),
+    (0xFC55, Isolated, &['\u{064A}', '\u{062C}']),
+    (0xFC56, Isolated, &['\u{064A}', '\u{062D}']),
+    (0xFC57, Isolated, &['\u{064A}', '\u{062E}']),
+    (0xFC58, Isolated, &['\u{064A}', '\u{0645}']),
+    (0xFC59, Isolated, &['\u{064A}', '\u{0649}']),
+    (0xFC5A, Isolated, &['\u{064A}', '\u{064A}']),
+    (0xFC5B, Isolated, &['\u{0630}', '\u{0670}']),
+    (0xFC5C, Isolated, &['\u{0631}', '\u{0670}']),
+    (0xFC5D, Isolated, &['\u{0649}', '\u{0670}']),
+    (0xFC64, Final, &['\u{0626}', '\u{0631}']),
+    (0xFC65, Final, &['\u{0626}', '\u{0632}']),
+    (0xFC66, Final, &['\u{0626}', '\u{0645}']),
+    (0xFC67, Final, &['\u{0626}', '\u{0646}']),
+    (0xFC68, Final, &['\u{0626}', '\u{0649}']),
+    (0xFC69, Final, &['\u{0626}', '\u{064A}']),
+    (0xFC6A, Final, &['\u{0628}', '\u{0631}']),
+    (0xFC6B, Final, &['\u{0628}', '\u{0632}']),
+    (0xFC6C, Final, &['\u{0628}', '\u{0645}']),
+    (0xFC6D, Final, &['\u{0628}', '\u{0646}']),
+    (0xFC6E, Final, &['\u{0628}', '\u{0649}']),
+    (0xFC6F, Final, &['\u{0628}', '\u{064A}']),
+    (0xFC70, Final, &['\u{062A}', '\u{0631}']),
+    (0xFC71, Final, &['\u{062A}', '\u{0632}']),
+    (0xFC72, Final, &['\u{062A}', '\u{0645}']),
+    (0xFC73, Final, &['\u{062A}', '\u{0646}']),
+    (0xFC74, Final, &['\u{062A}', '\u{0649}']),
+    (0xFC75, Final, &['\u{062A}', '\u{064A}']),
+    (0xFC76, Final, &['\u{062B}', '\u{0631}']),
+    (0xFC77, Final, &['\u{062B}', '\u{0632}']),
+    (0xFC78, Final, &['\u{062B}', '\u{0645}']),
+    (0xFC79, Final, &['\u{062B}', '\u{0646}']),
+    (0xFC7A, Final, &['\u{062B}', '\u{0649}']),
+    (0xFC7B, Final, &['\u{062B}', '\u{064A}']),
+    (0xFC7C, Final, &['\u{0641}', '\u{0649}']),
+    (0xFC7D, Final, &['\u{0641}', '\u{064A}']),
+    (0xFC7E, Final, &['\u{0642}', '\u{0649}']),
+    (0xFC7F, Final, &['\u{0642}', '\u{064A}']),
+    (0xFC80, Final, &['\u{0643}', '\u{0627}']),
+    (0xFC81, Final, &['\u{0643}', '\u{0644}']),
+    (0xFC82, Final, &['\u{0643}', '\u{0645}']),
+    (0xFC83, Final, &['\u{0643}', '\u{0649}']),
+    (0xFC84, Final, &['\u{0643}', '\u{064A}']),
+    (0xFC85, Final, &['\u{0644}', '\u{0645}']),
+    (0xFC86, Final, &['\u{0644}', '\u{0649}']),
+    (0xFC87, Final, &['\u{0644}', '\u{064A}']),
+    (0xFC88, Final, &['\u{0645}', '\u{0627}']),
+    (0xFC89, Final, &['\u{0645}', '\u{0645}']),
+    (0xFC8A, Final, &['\u{0646}', '\u{0631}']),
+    (0xFC8B, Final, &['\u{0646}', '\u{0632}']),
+    (0xFC8C, Final, &['\u{0646}', '\u{0645}']),
+    (0xFC8D, Final, &['\u{0646}', '\u{0646}']),
+    (0xFC8E, Final, &['\u{0646}', '\u{0649}']),
+    (0xFC8F, Final, &['\u{0646}', '\u{064A}']),
+    (0xFC90, Final, &['\u{0649}', '\u{0670}']),
+    (0xFC91, Final, &['\u{064A}', '\u{0631}']),
+    (0xFC92, Final, &['\u{064A}', '\u{0632}']),
+    (0xFC93, Final, &['\u{064A}', '\u{0645}']),
+    (0xFC94, Final, &['\u{064A}', '\u{0646}']),
+    (0xFC95, Final, &['\u{064A}', '\u{0649}']),
+    (0xFC96, Final, &['\u{064A}', '\u{064A}']),
+    (0xFC97, Initial, &['\u{0626}', '\u{062C}']),
+    (0xFC98, Initial, &['\u{0626}', '\u{062D}']),
+    (0xFC99, Initial, &['\u{0626}', '\u{062E}']),
+    (0xFC9A, Initial, &['\u{0626}', '\u{0645}']),
+    (0xFC9B, Initial, &['\u{0626}', '\u{0647}']),
+    (0xFC9C, Initial, &['\u{0628}', '\u{062C}']),
+    (0xFC9D, Initial, &['\u{0628}', '\u{062D}']),
+    (0xFC9E, Initial, &['\u{0628}', '\u{062E}']),
+    (0xFC9F, Initial, &['\u{0628}', '\u{0645}']),
+    (0xFCA0, Initial, &['\u{0628}', '\u{0647}']),
+    (0xFCA1, Initial, &['\u{062A}', '\u{062C}']),
+    (0xFCA2, Initial, &['\u{062A}', '\u{062D}']),
+    (0xFCA3, Initial, &['\u{062A}', '\u{062E}']),
+    (0xFCA4, Initial, &['\u{062A}', '\u{0645}']),
+    (0xFCA5, Initial, &['\u{062A}', '\u{0647}']),
+    (0xFCA6, Initial, &['\u{062B}', '\u{0645}']),
+    (0xFCA7, Initial, &['\u{062C}', '\u{062D}']),
+    (0xFCA8, Initial, &['\u{062C}', '\u{0645}']),
+    (0xFCA9, Initial, &['\u{062D}', '\u{062C}']),
+    (0xFCAA, Initial, &['\u{062D}', '\u{0645}']),
+    (0xFCAB, Initial, &['\u{062E}', '\u{062C}']),
+    (0xFCAC, Initial, &['\u{062E}', '\u{0645}']),
+    (0xFCAD, Initial, &['\u{0633}', '\u{062C}']),
+    (0xFCAE, Initial, &['\u{0633}', '\u{062D}']),
+    (0xFCAF, Initial, &['\u{0633}', '\u{062E}']),
+    (0xFCB0, Initial, &['\u{0633}', '\u{0645}']),
+    (0xFCB1, Initial, &['\u{0635}', '\u{062D}']),
+    (0xFCB2, Initial, &['\u{0635}', '\u{062E}']),
+    (0xFCB3, Initial, &['\u{0635}', '\u{0645}']),
+    (0xFCB4, Initial, &['\u{0636}', '\u{062C}']),
+    (0xFCB5, Initial, &['\u{0636}', '\u{062D}']),
+    (0xFCB6, Initial, &['\u{0636}', '\u{062E}']),
+    (0xFCB7, Initial, &['\u{0636}', '\u{0645}']),
+    (0xFCB8, Initial, &['\u{0637}', '\u{062D}']),
+    (0xFCB9, Initial, &['\u{0638}', '\u{0645}']),
+    (0xFCBA, Initial, &['\u{0639}', '\u{062C}']),
+    (0xFCBB, Initial, &['\u{0639}', '\u{0645}']),
+    (0xFCBC, Initial, &['\u{063A}', '\u{062C}']),
+    (0xFCBD, Initial, &['\u{063A}', '\u{0645}']),
+    (0xFCBE, Initial, &['\u{0641}', '\u{062C}']),
+    (0xFCBF, Initial, &['\u{0641}', '\u{062D}']),
+    (0xFCC0, Initial, &['\u{0641}', '\u{062E}']),
+    (0xFCC1, Initial, &['\u{0641}', '\u{0645}']),
+    (0xFCC2, Initial, &['\u{0642}', '\u{062D}']),
+    (0xFCC3, Initial, &['\u{0642}', '\u{0645}']),
+    (0xFCC4, Initial, &['\u{0643}', '\u{062C}']),
+    (0xFCC5, Initial, &['\u{0643}', '\u{062D}']),
+    (0xFCC6, Initial, &['\u{0643}', '\u{062E}']),
+    (0xFCC7, Initial, &['\u{0643}', '\u{0644}']),
+    (0xFCC8, Initial, &['\u{0643}', '\u{0645}']),
+    (0xFCC9, Initial, &['\u{0644}', '\u{062C}']),
+    (0xFCCA, Initial, &['\u{0644}', '\u{062D}']),
+    (0xFCCB, Initial, &['\u{0644}', '\u{062E}']),
+    (0xFCCC, Initial, &['\u{0644}', '\u{0645}']),
+    (0xFCCD, Initial, &['\u{0644}', '\u{0647}']),
+    (0xFCCE, Initial, &['\u{0645}', '\u{062C}']),
+    (0xFCCF, Initial, &['\u{0645}', '\u{062D}']),
+    (0xFCD0, Initial, &['\u{0645}', '\u{062E}']),
+    (0xFCD1, Initial, &['\u{0645}', '\u{0645}']),
+    (0xFCD2, Initial, &['\u{0646}', '\u{062C}']),
+    (0xFCD3, Initial, &['\u{0646}', '\u{062D}']),
+    (0xFCD4, Initial, &['\u{0646}', '\u{062E}']),
+    (0xFCD5, Initial, &['\u{0646}', '\u{0645}']),
+    (0xFCD6, Initial, &['\u{0646}', '\u{0647}']),
+    (0xFCD7, Initial, &['\u{0647}', '\u{062C}']),
+    (0xFCD8, Initial, &['\u{0647}', '\u{0645}']),
+    (0xFCD9, Initial, &['\u{0647}', '\u{0670}']),
+    (0xFCDA, Initial, &['\u{064A}', '\u{062C}']),
+    (0xFCDB, Initial, &['\u{064A}', '\u{062D}']),
+    (0xFCDC, Initial, &['\u{064A}', '\u{062E}']),
+    (0xFCDD, Initial, &['\u{064A}', '\u{0645}']),
+    (0xFCDE, Initial, &['\u{064A}', '\u{0647}']),
+    (0xFCDF, Medial, &['\u{0626}', '\u{0645}']),
+    (0xFCE0, Medial, &['\u{0626}', '\u{0647}']),
+    (0xFCE1, Medial, &['\u{0628}', '\u{0645}']),
+    (0xFCE2, Medial, &['\u{0628}', '\u{0647}']),
+    (0xFCE3, Medial, &['\u{062A}', '\u{0645}']),
+    (0xFCE4, Medial, &['\u{062A}', '\u{0647}']),
+    (0xFCE5, Medial, &['\u{062B}', '\u{0645}']),
+    (0xFCE6, Medial, &['\u{062B}', '\u{0647}']),
+    (0xFCE7, Medial, &['\u{0633}', '\u{0645}']),
+    (0xFCE8, Medial, &['\u{0633}', '\u{0647}']),
+    (0xFCE9, Medial, &['\u{0634}', '\u{0645}']),
+    (0xFCEA, Medial, &['\u{0634}', '\u{0647}']),
+    (0xFCEB, Medial, &['\u{0643}', '\u{0644}']),
+    (0xFCEC, Medial, &['\u{0643}', '\u{0645}']),
+    (0xFCED, Medial, &['\u{0644}', '\u{0645}']),
+    (0xFCEE, Medial, &['\u{0646}', '\u{0645}']),
+    (0xFCEF, Medial, &['\u{0646}', '\u{0647}']),
+    (0xFCF0, Medial, &['\u{064A}', '\u{0645}']),
+    (0xFCF1, Medial, &['\u{064A}', '\u{0647}']),
+    (0xFCF2, Medial, &['\u{0640}', '\u{064E}', '\u{0651}']),
+    (0xFCF3, Medial, &['\u{0640}', '\u{064F}', '\u{0651}']),
+    (0xFCF4, Medial, &['\u{0640}', '\u{0650}', '\u{0651}']),
+    (0xFCF5, Isolated, &['\u{0637}', '\u{0649}']),
+    (0xFCF6, Isolated, &['\u{0637}', '\u{064A}']),
+    (0xFCF7, Isolated, &['\u{0639}', '\u{0649}']),
+    (0xFCF8, Isolated, &['\u{0639}', '\u{064A}']),
+    (0xFCF9, Isolated, &['\u{063A}', '\u{0649}']),
+    (0xFCFA, Isolated, &['\u{063A}', '\u{064A}']),
+    (0xFCFB, Isolated, &['\u{0633}', '\u{0649}']),
+    (0xFCFC, Isolated, &['\u{0633}', '\u{064A}']),
+    (0xFCFD, Isolated, &['\u{0634}', '\u{0649}']),
+    (0xFCFE, Isolated, &['\u{0634}', '\u{064A}']),
+    (0xFCFF, Isolated, &['\u{062D}', '\u{0649}']),
+    (0xFD00, Isolated, &['\u{062D}', '\u{064A}']),
+    (0xFD01, Isolated, &['\u{062C}', '\u{0649}']),
+    (0xFD02, Isolated, &['\u{062C}', '\u{064A}']),
+    (0xFD03, Isolated, &['\u{062E}', '\u{0649}']),
+    (0xFD04, Isolated, &['\u{062E}', '\u{064A}']),
+    (0xFD05, Isolated, &['\u{0635}', '\u{0649}']),
+    (0xFD06, Isolated, &['\u{0635}', '\u{064A}']),
+    (0xFD07, Isolated, &['\u{0636}', '\u{0649}']),
+    (0xFD08, Isolated, &['\u{0636}', '\u{064A}']),
+    (0xFD09, Isolated, &['\u{0634}', '\u{062C}']),
+    (0xFD0A, Isolated, &['\u{0634}', '\u{062D}']),
+    (0xFD0B, Isolated, &['\u{0634}', '\u{062E}']),
+    (0xFD0C, Isolated, &['\u{0634}', '\u{0645}']),
+    (0xFD0D, Isolated, &['\u{0634}', '\u{0631}']),
+    (0xFD0E, Isolated, &['\u{0633}', '\u{0631}']),
+    (0xFD0F, Isolated, &['\u{0635}', '\u{0631}']),
+    (0xFD10, Isolated, &['\u{0636}', '\u{0631}']),
+    (0xFD11, Final, &['\u{0637}', '\u{0649}']),
+    (0xFD12, Final, &['\u{0637}', '\u{064A}']),
+    (0xFD13, Final, &['\u{0639}', '\u{0649}']),
+    (0xFD14, Final, &['\u{0639}', '\u{064A}']),
+    (0xFD15, Final, &['\u{063A}', '\u{0649}']),
+    (0xFD16, Final, &['\u{063A}', '\u{064A}']),
+    (0xFD17, Final, &['\u{0633}', '\u{0649}']),
+    (0xFD18, Final, &['\u{0633}', '\u{064A}']),
+    (0xFD19, Final, &['\u{0634}', '\u{0649}']),
+    (0xFD1A, Final, &['\u{0634}', '\u{064A}']),
+    (0xFD1B, Final, &['\u{062D}', '\u{0649}']),
+    (0xFD1C, Final, &['\u{062D}', '\u{064A}']),
+    (0xFD1D, Final, &['\u{062C}', '\u{0649}']),
+    (0xFD1E, Final, &['\u{062C}', '\u{064A}']),
+    (0xFD1F, Final, &['\u{062E}', '\u{0649}']),
+    (0xFD20, Final, &['\u{062E}', '\u{064A}']),
+    (0xFD21, Final, &['\u{0635}', '\u{0649}']),
+    (0xFD22, Final, &['\u{0635}', '\u{064A}']),
+    (0xFD23, Final, &['\u{0636}', '\u{0649}']),
+    (0xFD24, Final, &['\u{0636}', '\u{064A}']),
+    (0xFD25, Final, &['\u{0634}', '\u{062C}']),
+    (0xFD26, Final, &['\u{0634}', '\u{062D}']),
+    (0xFD27, Final, &['\u{0634}', '\u{062E}']),
+    (0xFD28, Final, &['\u{0634}', '\u{0645}']),
+    (0xFD29, Final, &['\u{0634}', '\u{0631}']),
+    (0xFD2A, Final, &['\u{0633}', '\u{0631}']),
+    (0xFD2B, Final, &['\u{0635}', '\u{0631}']),
+    (0xFD2C, Final, &['\u{0636}', '\u{0631}']),
+    (0xFD2D, Initial, &['\u{0634}', '\u{062C}']),
+    (0xFD2E, Initial, &['\u{0634}', '\u{062D}']),
+    (0xFD2F, Initial, &['\u{0634}', '\u{062E}']),
+    (0xFD30, Initial, &['\u{0634}', '\u{0645}']),
+    (0xFD31, Initial, &['\u{0633}', '\u{0647}']),
+    (0xFD32, Initial, &['\u{0634}', '\u{0647}']),
+    (0xFD33, Initial, &['\u{0637}', '\u{0645}']),
+    (0xFD34, Medial, &['\u{0633}', '\u{062C}']),
+    (0xFD35, Medial, &['\u{0633}', '\u{062D}']),
+    (0xFD36, Medial, &['\u{0633}', '\u{062E}']),
+    (0xFD37, Medial, &['\u{0634}', '\u{062C}']),
+    (0xFD38, Medial, &['\u{0634}', '\u{062D}']),
+    (0xFD39, Medial, &['\u{0634}', '\u{062E}']),
+    (0xFD3A, Medial, &['\u{0637}', '\u{0645}']),
+    (0xFD3B, Medial, &['\u{0638}', '\u{0645}']),
+    (0xFD3C, Final, &['\u{0627}', '\u{064B}']),
+    (0xFD3D, Isolated, &['\u{0627}', '\u{064B}']),
+    (0xFD50, Initial, &['\u{062A}', '\u{062C}', '\u{0645}']),
+    (0xFD51, Final, &['\u{062A}', '\u{062D}', '\u{062C}']),
+    (0xFD52, Initial, &['\u{062A}', '\u{062D}', '\u{062C}']),
+    (0xFD53, Initial, &['\u{062A}', '\u{062D}', '\u{0645}']),
+    (0xFD54, Initial, &['\u{062A}', '\u{062E}', '\u{0645}']),
+    (0xFD55, Initial, &['\u{062A}', '\u{0645}', '\u{062C}']),
+    (0xFD56, Initial, &['\u{062A}', '\u{0645}', '\u{062D}']),
+    (0xFD57, Initial, &['\u{062A}', '\u{0645}', '\u{062E}']),
+    (0xFD58, Final, &['\u{062C}', '\u{0645}', '\u{062D}']),
+    (0xFD59, Initial, &['\u{062C}', '\u{0645}', '\u{062D}']),
+    (0xFD5A, Final, &['\u{062D}', '\u{0645}', '\u{064A}']),
+    (0xFD5B, Final, &['\u{062D}', '\u{0645}', '\u{0649}']),
+    (0xFD5C, Initial, &['\u{0633}', '\u{062D}', '\u{062C}']),
+    (0xFD5D, Initial, &['\u{0633}', '\u{062C}', '\u{062D}']),
+    (0xFD5E, Final, &['\u{0633}', '\u{062C}', '\u{0649}']),
+    (0xFD5F, Final, &['\u{0633}', '\u{0645}', '\u{062D}']),
+    (0xFD60, Initial, &['\u{0633}', '\u{0645}', '\u{062D}']),
+    (0xFD61, Initial, &['\u{0633}', '\u{0645}', '\u{062C}']),
+    (0xFD62, Final, &['\u{0633}', '\u{0645}', '\u{0645}']),
+    (0xFD63, Initial, &['\u{0633}', '\u{0645}', '\u{0645}']),
+    (0xFD64, Final, &['\u{0635}', '\u{062D}', '\u{062D}']),
+    (0xFD65, Initial, &['\u{0635}', '\u{062D}', '\u{062D}']),
+    (0xFD66, Final, &['\u{0635}', '\u{0645}', '\u{0645}']),
+    (0xFD67, Final, &['\u{0634}', '\u{062D}', '\u{0645}']),
+    (0xFD68, Initial, &['\u{0634}', '\u{062D}', '\u{0645}']),
+    (0xFD69, Final, &['\u{0634}', '\u{062C}', '\u{064A}']),
+    (0xFD6A, Final, &['\u{0634}', '\u{0645}', '\u{062E}']),
+    (0xFD6B, Initial, &['\u{0634}', '\u{0645}', '\u{062E}']),
+    (0xFD6C, Final, &['\u{0634}', '\u{0645}', '\u{0645}']),
+    (0xFD6D, Initial, &['\u{0634}', '\u{0645}', '\u{0645}']),
+    (0xFD6E, Final, &['\u{0636}', '\u{062D}', '\u{0649}']),
+    (0xFD6F, Final, &['\u{0636}', '\u{062E}', '\u{0645}']),
+    (0xFD70, Initial, &['\u{0636}', '\u{062E}', '\u{0645}']),
+    (0xFD71, Final, &['\u{0637}', '\u{0645}', '\u{062D}']),
+    (0xFD72, Initial, &['\u{0637}', '\u{0645}', '\u{062D}']),
+    (0xFD73, Initial, &['\u{0637}', '\u{0645}', '\u{0645}']),
+    (0xFD74, Final, &['\u{0637}', '\u{0645}', '\u{064A}']),
+    (0xFD75, Final, &['\u{0639}', '\u{062C}', '\u{0645}']),
+    (0xFD76, Final, &['\u{0639}', '\u{0645}', '\u{0645}']),
+    (0xFD77, Initial, &['\u{0639}', '\u{0645}', '\u{0645}']),
+    (0xFD78, Final, &['\u{0639}', '\u{0645}', '\u{0649}']),
+    (0xFD79, Final, &['\u{063A}', '\u{0645}', '\u{0645}']),
+    (0xFD7A, Final, &['\u{063A}', '\u{0645}', '\u{064A}']),
+    (0xFD7B, Final, &['\u{063A}', '\u{0645}', '\u{0649}']),
+    (0xFD7C, Final, &['\u{0641}', '\u{062E}', '\u{0645}']),
+    (0xFD7D, Initial, &['\u{0641}', '\u{062E}', '\u{0645}']),
+    (0xFD7E, Final, &['\u{0642}', '\u{0645}', '\u{062D}']),
+    (0xFD7F, Final, &['\u{0642}', '\u{0645}', '\u{0645}']),
+    (0xFD80, Final, &['\u{0644}', '\u{062D}', '\u{0645}']),
+    (0xFD81, Final, &['\u{0644}', '\u{062D}', '\u{064A}']),
+    (0xFD82, Final, &['\u{0644}', '\u{062D}', '\u{0649}']),
+    (0xFD83, Initial, &['\u{0644}', '\u{062C}', '\u{062C}']),
+    (0xFD84, Final, &['\u{0644}', '\u{062C}', '\u{062C}']),
+    (0xFD85, Final, &['\u{0644}', '\u{062E}', '\u{0645}']),
+    (0xFD86, Initial, &['\u{0644}', '\u{062E}', '\u{0645}']),
+    (0xFD87, Final, &['\u{0644}', '\u{0645}', '\u{062D}']),
+    (0xFD88, Initial, &['\u{0644}', '\u{0645}', '\u{062D}']),
+    (0xFD89, Initial, &['\u{0645}', '\u{062D}', '\u{062C}']),
+    (0xFD8A, Initial, &['\u{0645}', '\u{062D}', '\u{0645}']),
+    (0xFD8B, Final, &['\u{0645}', '\u{062D}', '\u{064A}']),
+    (0xFD8C, Initial, &['\u{0645}', '\u{062C}', '\u{062D}']),
+    (0xFD8D, Initial, &['\u{0645}', '\u{062C}', '\u{0645}']),
+    (0xFD8E, Initial, &['\u{0645}', '\u{062E}', '\u{062C}']),
+    (0xFD8F, Initial, &['\u{0645}', '\u{062E}', '\u{0645}']),
+    (0xFD92, Initial, &['\u{0645}', '\u{062C}', '\u{062E}']),
+    (0xFD93, Initial, &['\u{0647}', '\u{0645}', '\u{062C}']),
+    (0xFD94, Initial, &['\u{0647}', '\u{0645}', '\u{0645}']),
+    (0xFD95, Initial, &['\u{0646}', '\u{062D}', '\u{0645}']),
+    (0xFD96, Final, &['\u{0646}', '\u{062D}', '\u{0649}']),
+    (0xFD97, Final, &['\u{0646}', '\u{062C}', '\u{0645}']),
+    (0xFD98, Initial, &['\u{0646}', '\u{062C}', '\u{0645}']),
+    (0xFD99, Final, &['\u{0646}', '\u{062C}', '\u{0649}']),
+    (0xFD9A, Final, &['\u{0646}', '\u{0645}', '\u{064A}']),
+    (0xFD9B, Final, &['\u{0646}', '\u{0645}', '\u{0649}']),
+    (0xFD9C, Final, &['\u{064A}', '\u{0645}', '\u{0645}']),
+    (0xFD9D, Initial, &['\u{064A}', '\u{0645}', '\u{0645}']),
+    (0xFD9E, Final, &['\u{0628}', '\u{062E}', '\u{064A}']),
+    (0xFD9F, Final, &['\u{062A}', '\u{062C}', '\u{064A}']),
+    (0xFDA0, Final, &['\u{062A}', '\u{062C}', '\u{0649}']),
+    (0xFDA1, Final, &['\u{062A}', '\u{062E}', '\u{064A}']),
+    (0xFDA2, Final, &['\u{062A}', '\u{062E}', '\u{0649}']),
+    (0xFDA3, Final, &['\u{062A}', '\u{0645}', '\u{064A}']),
+    (0xFDA4, Final, &['\u{062A}', '\u{0645}', '\u{0649}']),
+    (0xFDA5, Final, &['\u{062C}', '\u{0645}', '\u{064A}']),
+    (0xFDA6, Final, &['\u{062C}', '\u{062D}', '\u{0649}']),
+    (0xFDA7, Final, &['\u{062C}', '\u{0645}', '\u{0649}']),
+    (0xFDA8, Final, &['\u{0633}', '\u{062E}', '\u{0649}']),
+    (0xFDA9, Final, &['\u{0635}', '\u{062D}', '\u{064A}']),
+    (0xFDAA, Final, &['\u{0634}', '\u{062D}', '\u{064A}']),
+    (0xFDAB, Final, &['\u{0636}', '\u{062D}', '\u{064A}']),
+    (0xFDAC, Final, &['\u{0644}', '\u{062C}', '\u{064A}']),
+    (0xFDAD, Final, &['\u{0644}', '\u{0645}', '\u{064A}']),
+    (0xFDAE, Final, &['\u{064A}', '\u{062D}', '\u{064A}']),
+    (0xFDAF, Final, &['\u{064A}', '\u{062C}', '\u{064A}']),
+    (0xFDB0, Final, &['\u{064A}', '\u{0645}', '\u{064A}']),
+    (0xFDB1, Final, &['\u{0645}', '\u{0645}', '\u{064A}']),
+    (0xFDB2, Final, &['\u{0642}', '\u{0645}', '\u{064A}']),
+    (0xFDB3, Final, &['\u{0646}', '\u{062D}', '\u{064A}']),
+    (0xFDB4, Initial, &['\u{0642}', '\u{0645}', '\u{062D}']),
+    (0xFDB5, Initial, &['\u{0644}', '\u{062D}', '\u{0645}']),
+    (0xFDB6, Final, &['\u{0639}', '\u{0645}', '\u{064A}']),
+    (0xFDB7, Final, &['\u{0643}', '\u{0645}', '\u{064A}']),
+    (0xFDB8, Initial, &['\u{0646}', '\u{062C}', '\u{062D}']),
+    (0xFDB9, Final, &['\u{0645}', '\u{062E}', '\u{064A}']),
+    (0xFDBA, Initial, &['\u{0644}', '\u{062C}', '\u{0645}']),
+    (0xFDBB, Final, &['\u{0643}', '\u{0645}', '\u{0645}']),
+    (0xFDBC, Final, &['\u{0644}', '\u{062C}', '\u{0645}']),
+    (0xFDBD, Final, &['\u{0646}', '\u{062C}', '\u{062D}']),
+    (0xFDBE, Final, &['\u{062C}', '\u{062D}', '\u{064A}']),
+    (0xFDBF, Final, &['\u{062D}', '\u{062C}', '\u{064A}']),
+    (0xFDC0, Final, &['\u{0645}', '\u{062C}', '\u{064A}']),
+    (0xFDC1, Final, &['\u{0641}', '\u{0645}', '\u{064A}']),
+    (0xFDC2, Final, &['\u{0628}', '\u{062D}', '\u{064A}']),
+    (0xFDC3, Initial, &['\u{0643}', '\u{0645}', '\u{0645}']),
+    (0xFDC4, Initial, &['\u{0639}', '\u{062C}', '\u{0645}']),
+    (0xFDC5, Initial, &['\u{0635}', '\u{0645}', '\u{0645}']),
+    (0xFDC6, Final, &['\u{0633}', '\u{062E}', '\u{064A}']),
+    (0xFDC7, Final, &['\u{0646}', '\u{062C}', '\u{064A}']),
+    (0xFDF0, Isolated, &['\u{0635}', '\u{0644}', '\u{06D2}']),
+    (0xFDF1, Isolated, &['\u{0642}', '\u{0644}', '\u{06D2}']),
+    (0xFDF2, Isolated, &['\u{0627}', '\u{0644}', '\u{0644}', '\u{0647}']),
+    (0xFDF3, Isolated, &['\u{0627}', '\u{0643}', '\u{0628}', '\u{0631}']),
+    (0xFDF4, Isolated, &['\u{0645}', '\u{062D}', '\u{0645}', '\u{062F}']),
+    (0xFDF5, Isolated, &['\u{0635}', '\u{0644}', '\u{0639}', '\u{0645}']),
+    (0xFDF6, Isolated, &['\u{0631}', '\u{0633}', '\u{0648}', '\u{0644}']),
+    (0xFDF7, Isolated, &['\u{0639}', '\u{0644}', '\u{064A}', '\u{0647}']),
+    (0xFDF8, Isolated, &['\u{0648}', '\u{0633}', '\u{0644}', '\u{0645}']),
+    (0xFDF9, Isolated, &['\u{0635}', '\u{0644}', '\u{0649}']),
+    (0xFDFC, Isolated, &['\u{0631}', '\u{06CC}', '\u{0627}', '\u{0644}']),
+    (0xFE71, Medial, &['\u{0640}', '\u{064B}']),
+    (0xFE77, Medial, &['\u{0640}', '\u{064E}']),
+    (0xFE79, Medial, &['\u{0640}', '\u{064F}']),
+    (0xFE7B, Medial, &['\u{0640}', '\u{0650}']),
+    (0xFE7D, Medial, &['\u{0640}', '\u{0651}']),
+    (0xFE7F, Medial, &['\u{0640}', '\u{0652}']),
+    (0xFE80, Isolated, &['\u{0621}']),
+    (0xFE81, Isolated, &['\u{0622}']),
+    (0xFE82, Final, &['\u{0622}']),
+    (0xFE83, Isolated, &['\u{0623}']),
+    (0xFE84, Final, &['\u{0623}']),
+    (0xFE85, Isolated, &['\u{0624}']),
+    (0xFE86, Final, &['\u{0624}']),
+    (0xFE87, Isolated, &['\u{0625}']),
+    (0xFE88, Final, &['\u{0625}']),
+    (0xFE89, Isolated, &['\u{0626}']),
+    (0xFE8A, Final, &['\u{0626}']),
+    (0xFE8B, Initial, &['\u{0626}']),
+    (0xFE8C, Medial, &['\u{0626}']),
+    (0xFE8D, Isolated, &['\u{0627}']),
+    (0xFE8E, Final, &['\u{0627}']),
+    (0xFE8F, Isolated, &['\u{0628}']),
+    (0xFE90, Final, &['\u{0628}']),
+    (0xFE91, Initial, &['\u{0628}']),
+    (0xFE92, Medial, &['\u{0628}']),
+    (0xFE93, Isolated, &['\u{0629}']),
+    (0xFE94, Final, &['\u{0629}']),
+    (0xFE95, Isolated, &['\u{062A}']),
+    (0xFE96, Final, &['\u{062A}']),
+    (0xFE97, Initial, &['\u{062A}']),
+    (0xFE98, Medial, &['\u{062A}']),
+    (0xFE99, Isolated, &['\u{062B}']),
+    (0xFE9A, Final, &['\u{062B}']),
+    (0xFE9B, Initial, &['\u{062B}']),
+    (0xFE9C, Medial, &['\u{062B}']),
+    (0xFE9D, Isolated, &['\u{062C}']),
+    (0xFE9E, Final, &['\u{062C}']),
+    (0xFE9F, Initial, &['\u{062C}']),
+    (0xFEA0, Medial, &['\u{062C}']),
+    (0xFEA1, Isolated, &['\u{062D}']),
+    (0xFEA2, Final, &['\u{062D}']),
+    (0xFEA3, Initial, &['\u{062D}']),
+    (0xFEA4, Medial, &['\u{062D}']),
+    (0xFEA5, Isolated, &['\u{062E}']),
+    (0xFEA6, Final, &['\u{062E}']),
+    (0xFEA7, Initial, &['\u{062E}']),
+    (0xFEA8, Medial, &['\u{062E}']),
+    (0xFEA9, Isolated, &['\u{062F}']),
+    (0xFEAA, Final, &['\u{062F}']),
+    (0xFEAB, Isolated, &['\u{0630}']),
+    (0xFEAC, Final, &['\u{0630}']),
+    (0xFEAD, Isolated, &['\u{0631}']),
+    (0xFEAE, Final, &['\u{0631}']),
+    (0xFEAF, Isolated, &['\u{0632}']),
+    (0xFEB0, Final, &['\u{0632}']),
+    (0xFEB1, Isolated, &['\u{0633}']),
+    (0xFEB2, Final, &['\u{0633}']),
+    (0xFEB3, Initial, &['\u{0633}']),
+    (0xFEB4, Medial, &['\u{0633}']),
+    (0xFEB5, Isolated, &['\u{0634}']),
+    (0xFEB6, Final, &['\u{0634}']),
+    (0xFEB7, Initial, &['\u{0634}']),
+    (0xFEB8, Medial, &['\u{0634}']),
+    (0xFEB9, Isolated, &['\u{0635}']),
+    (0xFEBA, Final, &['\u{0635}']),
+    (0xFEBB, Initial, &['\u{0635}']),
+    (0xFEBC, Medial, &['\u{0635}']),
+    (0xFEBD, Isolated, &['\u{0636}']),
+    (0xFEBE, Final, &['\u{0636}']),
+    (0xFEBF, Initial, &['\u{0636}']),
+    (0xFEC0, Medial, &['\u{0636}']),
+    (0xFEC1, Isolated, &['\u{0637}']),
+    (0xFEC2, Final, &['\u{0637}']),
+    (0xFEC3, Initial, &['\u{0637}']),
+    (0xFEC4, Medial, &['\u{0637}']),
+    (0xFEC5, Isolated, &['\u{0638}']),
+    (0xFEC6, Final, &['\u{0638}']),
+    (0xFEC7, Initial, &['\u{0638}']),
+    (0xFEC8, Medial, &['\u{0638}']),
+    (0xFEC9, Isolated, &['\u{0639}']),
+    (0xFECA, Final, &['\u{0639}']),
+    (0xFECB, Initial, &['\u{0639}']),
+    (0xFECC, Medial, &['\u{0639}']),
+    (0xFECD, Isolated, &['\u{063A}']),
+    (0xFECE, Final, &['\u{063A}']),
+    (0xFECF, Initial, &['\u{063A}']),
+    (0xFED0, Medial, &['\u{063A}']),
+    (0xFED1, Isolated, &['\u{0641}']),
+    (0xFED2, Final, &['\u{0641}']),
+    (0xFED3, Initial, &['\u{0641}']),
+    (0xFED4, Medial, &['\u{0641}']),
+    (0xFED5, Isolated, &['\u{0642}']),
+    (0xFED6, Final, &['\u{0642}']),
+    (0xFED7, Initial, &['\u{0642}']),
+    (0xFED8, Medial, &['\u{0642}']),
+    (0xFED9, Isolated, &['\u{0643}']),
+    (0xFEDA, Final, &['\u{0643}']),
+    (0xFEDB, Initial, &['\u{0643}']),
+    (0xFEDC, Medial, &['\u{0643}']),
+    (0xFEDD, Isolated, &['\u{0644}']),
+    (0xFEDE, Final, &['\u{0644}']),
+    (0xFEDF, Initial, &['\u{0644}']),
+    (0xFEE0, Medial, &['\u{0644}']),
+    (0xFEE1, Isolated, &['\u{0645}']),
+    (0xFEE2, Final, &['\u{0645}']),
+    (0xFEE3, Initial, &['\u{0645}']),
+    (0xFEE4, Medial, &['\u{0645}']),
+    (0xFEE5, Isolated, &['\u{0646}']),
+    (0xFEE6, Final, &['\u{0646}']),
+    (0xFEE7, Initial, &['\u{0646}']),
+    (0xFEE8, Medial, &['\u{0646}']),
+    (0xFEE9, Isolated, &['\u{0647}']),
+    (0xFEEA, Final, &['\u{0647}']),
+    (0xFEEB, Initial, &['\u{0647}']),
+    (0xFEEC, Medial, &['\u{0647}']),
+    (0xFEED, Isolated, &['\u{0648}']),
+    (0xFEEE, Final, &['\u{0648}']),
+    (0xFEEF, Isolated, &['\u{0649}']),
+    (0xFEF0, Final, &['\u{0649}']),
+    (0xFEF1, Isolated, &['\u{064A}']),
+    (0xFEF2, Final, &['\u{064A}']),
+    (0xFEF3, Initial, &['\u{064A}']),
+    (0xFEF4, Medial, &['\u{064A}']),
+    (0xFEF5, Isolated, &['\u{0644}', '\u{0622}']),
+    (0xFEF6, Final, &['\u{0644}', '\u{0622}']),
+    (0xFEF7, Isolated, &['\u{0644}', '\u{0623}']),
+    (0xFEF8, Final, &['\u{0644}', '\u{0623}']),
+    (0xFEF9, Isolated, &['\u{0644}', '\u{0625}']),
+    (0xFEFA, Final, &['\u{0644}', '\u{0625}']),
+    (0xFEFB, Isolated, &['\u{0644}', '\u{0627}']),
+    (0xFEFC, Final, &['\u{0644}', '\u{0627}']),
+];
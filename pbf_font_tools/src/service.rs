@@ -0,0 +1,378 @@
+//! An [axum] router for serving composite glyph ranges over HTTP.
+//!
+//! The router returned by [`GlyphService::into_router`] implements `tower::Service`, so it can
+//! be served on its own or nested inside an existing tile server. It exposes two routes:
+//!
+//! * `/{fontstack}/{start}-{end}.pbf` - A glyph range, where `fontstack` is a (URL encoded)
+//!   comma-separated list of font names, in order of precedence.
+//! * `/fonts.json` - A JSON list of the fonts available in the store.
+//!
+//! Where the glyphs come from ([`GlyphStore`]), whether and how they are cached
+//! ([`GlyphCache`] and the `Cache-Control` header), and how responses are compressed
+//! ([`Compressor`]) are all left up to the caller.
+
+use std::future::Future;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::header::{
+    ACCEPT_ENCODING, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY,
+};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use futures::future::join_all;
+use protobuf::Message;
+use tokio::task::spawn_blocking;
+
+#[cfg(feature = "freetype")]
+use crate::fallback::FallbackRenderer;
+use crate::proto::glyphs::Glyphs;
+use crate::tools::{combine_glyphs, empty_glyphs};
+use crate::PbfFontError;
+
+/// A source of pre-rendered glyph ranges.
+pub trait GlyphStore: Send + Sync + 'static {
+    /// Returns `true` if the store has glyphs for the named font.
+    fn has_font(&self, font_name: &str) -> impl Future<Output = bool> + Send;
+
+    /// Loads a single glyph range for the named font.
+    ///
+    /// Ranges which the font does not cover should be reported as a
+    /// [`PbfFontError::IoError`] of kind [`io::ErrorKind::NotFound`] and are skipped.
+    /// Any other error fails the request with a `500` status.
+    fn load_glyphs(
+        &self,
+        font_name: &str,
+        start: u32,
+        end: u32,
+    ) -> impl Future<Output = Result<Glyphs, PbfFontError>> + Send;
+
+    /// Lists the names of all fonts in the store.
+    fn font_names(&self) -> impl Future<Output = io::Result<Vec<String>>> + Send;
+}
+
+/// A [`GlyphStore`] backed by a directory laid out like the output of `build_pbf_glyphs`.
+///
+/// Fonts are assumed to be stored in `<path>/<font_name>/<start>-<end>.pbf`.
+#[derive(Clone, Debug)]
+pub struct DirectoryStore {
+    path: PathBuf,
+    #[cfg(feature = "freetype")]
+    fallback: Option<Arc<FallbackRenderer>>,
+}
+
+impl DirectoryStore {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            #[cfg(feature = "freetype")]
+            fallback: None,
+        }
+    }
+
+    /// Renders ranges which are missing from the directory from source fonts.
+    ///
+    /// See [`load_or_render_glyphs`](crate::load_or_render_glyphs) for details.
+    #[cfg(feature = "freetype")]
+    #[must_use]
+    pub fn with_fallback(mut self, fallback: FallbackRenderer) -> Self {
+        self.fallback = Some(Arc::new(fallback));
+        self
+    }
+}
+
+impl GlyphStore for DirectoryStore {
+    async fn has_font(&self, font_name: &str) -> bool {
+        #[cfg(feature = "freetype")]
+        if let Some(fallback) = &self.fallback {
+            if fallback.source(font_name).is_some() {
+                return true;
+            }
+        }
+
+        tokio::fs::metadata(self.path.join(font_name))
+            .await
+            .is_ok_and(|metadata| metadata.is_dir())
+    }
+
+    async fn load_glyphs(
+        &self,
+        font_name: &str,
+        start: u32,
+        end: u32,
+    ) -> Result<Glyphs, PbfFontError> {
+        #[cfg(feature = "freetype")]
+        if let Some(fallback) = &self.fallback {
+            return crate::fallback::load_or_render_glyphs(
+                &self.path,
+                font_name,
+                start,
+                end,
+                fallback.clone(),
+            )
+            .await;
+        }
+
+        crate::tools::load_glyphs(&self.path, font_name, start, end).await
+    }
+
+    async fn font_names(&self) -> io::Result<Vec<String>> {
+        let mut entries = tokio::fs::read_dir(&self.path).await?;
+        let mut names = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_dir() {
+                if let Ok(name) = entry.file_name().into_string() {
+                    names.push(name);
+                }
+            }
+        }
+        #[cfg(feature = "freetype")]
+        if let Some(fallback) = &self.fallback {
+            names.extend(fallback.font_names().map(str::to_string));
+        }
+        names.sort();
+        names.dedup();
+
+        Ok(names)
+    }
+}
+
+/// A cache for encoded (but uncompressed) glyph range responses.
+///
+/// Keys are normalised, comma-separated font names followed by the range,
+/// like `Open Sans Regular,Arial Unicode MS Regular/0-255`.
+pub trait GlyphCache: Send + Sync + 'static {
+    fn get(&self, key: &str) -> Option<Bytes>;
+
+    fn insert(&self, key: &str, data: Bytes);
+}
+
+/// A [`GlyphCache`] that never stores anything. This is the default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoCache;
+
+impl GlyphCache for NoCache {
+    fn get(&self, _key: &str) -> Option<Bytes> {
+        None
+    }
+
+    fn insert(&self, _key: &str, _data: Bytes) {}
+}
+
+/// A response compression scheme.
+pub trait Compressor: Send + Sync + 'static {
+    /// The `Content-Encoding` token for this scheme, such as `gzip`.
+    fn encoding(&self) -> &'static str;
+
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>>;
+}
+
+/// Serves composite glyph ranges from a [`GlyphStore`].
+pub struct GlyphService<S> {
+    store: S,
+    cache: Arc<dyn GlyphCache>,
+    compressor: Option<Arc<dyn Compressor>>,
+    cache_control: Option<HeaderValue>,
+}
+
+impl<S: GlyphStore> GlyphService<S> {
+    /// Creates a new service with no caching, no compression,
+    /// and no `Cache-Control` header.
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            cache: Arc::new(NoCache),
+            compressor: None,
+            cache_control: None,
+        }
+    }
+
+    /// Sets the cache used for encoded glyph ranges.
+    #[must_use]
+    pub fn with_cache<C: GlyphCache>(mut self, cache: C) -> Self {
+        self.cache = Arc::new(cache);
+        self
+    }
+
+    /// Compresses responses for clients that accept the compressor's encoding.
+    #[must_use]
+    pub fn with_compressor<C: Compressor>(mut self, compressor: C) -> Self {
+        self.compressor = Some(Arc::new(compressor));
+        self
+    }
+
+    /// Sets the `Cache-Control` header sent with glyph range responses.
+    #[must_use]
+    pub fn with_cache_control(mut self, value: HeaderValue) -> Self {
+        self.cache_control = Some(value);
+        self
+    }
+
+    /// Builds an [axum] router for the service.
+    pub fn into_router(self) -> Router {
+        Router::new()
+            .route("/fonts.json", get(font_index::<S>))
+            .route("/{fontstack}/{range}", get(glyph_range::<S>))
+            .with_state(Arc::new(self))
+    }
+
+    /// Loads and combines the glyphs for a (validated) list of fonts.
+    async fn encoded_glyphs(
+        &self,
+        font_names: &[&str],
+        start: u32,
+        end: u32,
+    ) -> Result<Bytes, PbfFontError> {
+        let key = format!("{}/{start}-{end}", font_names.join(","));
+        if let Some(data) = self.cache.get(&key) {
+            return Ok(data);
+        }
+
+        let glyph_data = join_all(
+            font_names
+                .iter()
+                .map(|font| self.store.load_glyphs(font, start, end)),
+        )
+        .await
+        .into_iter()
+        .filter_map(|result| match result {
+            // Fonts often only cover a few ranges, so a missing range is not an error
+            Err(PbfFontError::IoError(e)) if e.kind() == io::ErrorKind::NotFound => None,
+            result => Some(result),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let stack_name = font_names.join(", ");
+        let glyphs = spawn_blocking(move || combine_glyphs(glyph_data))
+            .await?
+            .unwrap_or_else(|| empty_glyphs(stack_name, start, end));
+        let data = Bytes::from(glyphs.write_to_bytes()?);
+
+        self.cache.insert(&key, data.clone());
+
+        Ok(data)
+    }
+}
+
+async fn font_index<S: GlyphStore>(State(service): State<Arc<GlyphService<S>>>) -> Response {
+    match service.store.font_names().await {
+        Ok(names) => Json(names).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+async fn glyph_range<S: GlyphStore>(
+    State(service): State<Arc<GlyphService<S>>>,
+    Path((fontstack, range)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Response {
+    let Some((start, end)) = parse_range(&range) else {
+        return (StatusCode::BAD_REQUEST, "Invalid glyph range").into_response();
+    };
+
+    let font_names: Vec<&str> = fontstack
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect();
+    if font_names.is_empty() || !font_names.iter().all(|name| is_valid_font_name(name)) {
+        return (StatusCode::BAD_REQUEST, "Invalid fontstack").into_response();
+    }
+
+    let known_fonts = join_all(font_names.iter().map(|name| service.store.has_font(name))).await;
+    if !known_fonts.into_iter().any(|known| known) {
+        return (StatusCode::NOT_FOUND, "No matching fonts").into_response();
+    }
+
+    let data = match service.encoded_glyphs(&font_names, start, end).await {
+        Ok(data) => data,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let etag = etag_for(&data);
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("application/x-protobuf"),
+    );
+    if let Some(cache_control) = &service.cache_control {
+        response_headers.insert(CACHE_CONTROL, cache_control.clone());
+    }
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(ETAG, value);
+    }
+    if service.compressor.is_some() {
+        response_headers.insert(VARY, HeaderValue::from_static("Accept-Encoding"));
+    }
+
+    if headers
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag))
+    {
+        return (StatusCode::NOT_MODIFIED, response_headers).into_response();
+    }
+
+    if let Some(compressor) = &service.compressor {
+        if accepts_encoding(&headers, compressor.encoding()) {
+            if let Ok(compressed) = compressor.compress(&data) {
+                response_headers.insert(
+                    CONTENT_ENCODING,
+                    HeaderValue::from_static(compressor.encoding()),
+                );
+                return (response_headers, compressed).into_response();
+            }
+        }
+    }
+
+    (response_headers, data).into_response()
+}
+
+/// Parses a range like `256-511.pbf`, returning `None` unless it is a valid 256 glyph range.
+#[must_use]
+pub fn parse_range(range: &str) -> Option<(u32, u32)> {
+    let (start, end) = range.strip_suffix(".pbf")?.split_once('-')?;
+    let start: u32 = start.parse().ok()?;
+    let end: u32 = end.parse().ok()?;
+
+    (start % 256 == 0 && end == start + 255 && end < 65536).then_some((start, end))
+}
+
+/// Font names are commonly used as directory or object names, so we refuse anything that
+/// could escape the store's root.
+fn is_valid_font_name(name: &str) -> bool {
+    name != "." && name != ".." && !name.contains(['/', '\\', '\0'])
+}
+
+/// Computes a weak entity tag for the uncompressed response body. Weak tags are used since the
+/// same tag is used for every content encoding.
+///
+/// A CRC-32 of the body is used (rather than `std`'s hasher) so that tags stay the same across
+/// releases, restarts and replicas.
+fn etag_for(data: &[u8]) -> String {
+    format!("W/\"{:08x}-{:x}\"", crc32fast::hash(data), data.len())
+}
+
+fn accepts_encoding(headers: &HeaderMap, encoding: &str) -> bool {
+    headers
+        .get_all(ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|coding| {
+            let mut parts = coding.split(';').map(str::trim);
+            let name = parts.next().unwrap_or_default();
+            let rejected = parts.any(|param| {
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+            (name.eq_ignore_ascii_case(encoding) || name == "*") && !rejected
+        })
+}
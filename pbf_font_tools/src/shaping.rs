@@ -0,0 +1,291 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::ptr;
+
+use crate::error::PbfFontError;
+use crate::freetype::ffi::{FT_Byte, FT_Error, FT_Face, FT_Long, FT_ULong};
+use crate::ft_generate::{face_name, render_sdf_glyph_from_index};
+use crate::{freetype, Fontstack, Glyphs};
+
+/// The first code point of the (Basic Multilingual Plane) Private Use Area.
+pub const PRIVATE_USE_AREA_START: u32 = 0xE000;
+
+/// The last code point of the (Basic Multilingual Plane) Private Use Area.
+pub const PRIVATE_USE_AREA_END: u32 = 0xF8FF;
+
+extern "C" {
+    // Not (yet) exposed by freetype-sys
+    fn FT_Load_Sfnt_Table(
+        face: FT_Face,
+        tag: FT_ULong,
+        offset: FT_Long,
+        buffer: *mut FT_Byte,
+        length: *mut FT_ULong,
+    ) -> FT_Error;
+}
+
+/// Reads the entire font file backing an SFNT face.
+pub(crate) fn load_font_data(face: &freetype::Face) -> Option<Vec<u8>> {
+    if !face.is_sfnt() {
+        return None;
+    }
+
+    // Clones share the underlying FreeType face
+    let mut face = face.clone();
+    let raw_face = ptr::from_mut(face.raw_mut());
+    let mut length: FT_ULong = 0;
+    // SAFETY: the face is valid for the duration of the borrow, and a tag of zero loads the
+    // whole font file. The first call only queries the length, and the second writes exactly
+    // `length` bytes into a buffer of that size.
+    unsafe {
+        if FT_Load_Sfnt_Table(raw_face, 0, 0, ptr::null_mut(), &mut length) != 0 {
+            return None;
+        }
+        let mut data = vec![0; length as usize];
+        if FT_Load_Sfnt_Table(raw_face, 0, 0, data.as_mut_ptr(), &mut length) != 0 {
+            return None;
+        }
+        Some(data)
+    }
+}
+
+/// Finds the glyphs which replace those of the code points between `start` and `end`
+/// (inclusive) when the given features and language are applied, returning a map from code
+/// point to glyph index.
+///
+/// Each code point is shaped on its own, so only single substitutions have any effect.
+/// Code points whose glyph is unchanged are left out.
+pub(crate) fn substitute_features(
+    face: &freetype::Face,
+    start: u32,
+    end: u32,
+    features: &[rustybuzz::Feature],
+    language: Option<&rustybuzz::Language>,
+) -> HashMap<u32, u32> {
+    let mut glyphs = HashMap::new();
+    if features.is_empty() && language.is_none() {
+        return glyphs;
+    }
+
+    let mapped: Vec<char> = (start..=end)
+        .filter(|&code_point| face.get_char_index(code_point as usize).is_ok())
+        .filter_map(char::from_u32)
+        .collect();
+    // Avoid loading the font data when there is nothing to substitute
+    if mapped.is_empty() {
+        return glyphs;
+    }
+
+    let Some(font_data) = load_font_data(face) else {
+        return glyphs;
+    };
+    let face_index = (face.raw().face_index & 0xFFFF) as u32;
+    let Some(shaper_face) = rustybuzz::Face::from_slice(&font_data, face_index) else {
+        return glyphs;
+    };
+
+    let shape =
+        |c: char, features: &[rustybuzz::Feature], language: Option<&rustybuzz::Language>| {
+            let mut buffer = rustybuzz::UnicodeBuffer::new();
+            buffer.add(c, 0);
+            if let Some(language) = language {
+                buffer.set_language(language.clone());
+            }
+            buffer.guess_segment_properties();
+            match rustybuzz::shape(&shaper_face, features, buffer).glyph_infos() {
+                [info] if info.glyph_id != 0 => Some(info.glyph_id),
+                _ => None,
+            }
+        };
+
+    for c in mapped {
+        // Compare against the default shaping of the code point, rather than the glyph it
+        // maps to, so that only the requested features and language take effect (and not,
+        // for example, the hiding of default ignorable code points).
+        let Some(glyph_index) = shape(c, features, language) else {
+            continue;
+        };
+        if shape(c, &[], None) != Some(glyph_index) {
+            glyphs.insert(c as u32, glyph_index);
+        }
+    }
+
+    glyphs
+}
+
+/// A glyph as positioned by the shaper, in font units.
+///
+/// The same glyph may be positioned differently in different contexts (for example, a mark
+/// attached to different bases, or a letter followed by a kerning pair). Each distinct
+/// positioning is rendered as a separate glyph, since renderers only know about the advance
+/// of each glyph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct GlyphVariant {
+    glyph_id: u32,
+    x_advance: i32,
+    x_offset: i32,
+    y_offset: i32,
+}
+
+/// The result of shaping a set of labels with [`shape_labels`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShapedFont {
+    /// A fontstack containing every shaped glyph, keyed by the Private Use Area code point
+    /// assigned to it. The glyphs are sorted by code point.
+    pub stack: Fontstack,
+
+    /// Maps each label to the string of Private Use Area code points which renders it
+    /// (in visual order, so it must not be reordered by a bidi algorithm).
+    pub substitutions: HashMap<String, String>,
+
+    /// The labels which the font can't fully render (their shaping needs the `.notdef` glyph),
+    /// in the order given. These have no substitution, and none of their glyphs are rendered,
+    /// so renderers can fall back to another font for them.
+    pub unsupported: Vec<String>,
+}
+
+impl ShapedFont {
+    /// Splits the glyphs into the 256 glyph ranges requested by renderers, returning each
+    /// non-empty range along with its start and end code points.
+    #[must_use]
+    pub fn ranges(&self) -> Vec<(u32, u32, Glyphs)> {
+        let mut ranges: BTreeMap<u32, Fontstack> = BTreeMap::new();
+        for glyph in &self.stack.glyphs {
+            let start = glyph.id() & !0xFF;
+            ranges
+                .entry(start)
+                .or_insert_with(|| {
+                    let mut stack = Fontstack::new();
+                    stack.set_name(self.stack.name().to_string());
+                    stack.set_range(format!("{start}-{}", start + 255));
+                    stack
+                })
+                .glyphs
+                .push(glyph.clone());
+        }
+
+        ranges
+            .into_iter()
+            .map(|(start, stack)| {
+                let mut glyphs = Glyphs::new();
+                glyphs.stacks.push(stack);
+                (start, start + 255, glyphs)
+            })
+            .collect()
+    }
+}
+
+/// Shapes a set of labels with a HarfBuzz-compatible shaper, and renders the resulting
+/// glyphs into a fontstack keyed by Private Use Area code points.
+///
+/// Renderers like MapLibre look glyphs up by code point and lay them out one after another,
+/// which can't represent scripts where the glyphs depend on context, such as Arabic joining
+/// forms, Indic conjuncts or Myanmar clusters. Shaping the labels upfront and rendering the
+/// shaped glyphs (including their positioning) sidesteps that. Labels must then be rewritten
+/// using [`ShapedFont::substitutions`] before rendering, using a fontstack which lists the
+/// shaped font first.
+///
+/// Each label is shaped as a single run, with the direction, script and language guessed
+/// from its contents. Code points are assigned in order of first appearance, starting at
+/// [`PRIVATE_USE_AREA_START`]. Labels which the font can't cover are listed in
+/// [`ShapedFont::unsupported`] rather than rendered as tofu. An error is returned if the labels
+/// need more distinct glyphs than fit in the Private Use Area.
+///
+/// See [`glyph_range_for_face`](crate::glyph_range_for_face) for details on the
+/// `size`, `radius` and `cutoff` parameters.
+pub fn shape_labels<P: AsRef<Path>, S: AsRef<str>>(
+    font_path: P,
+    face_index: isize,
+    labels: &[S],
+    size: usize,
+    radius: usize,
+    cutoff: f64,
+) -> Result<ShapedFont, PbfFontError> {
+    let font_data = std::fs::read(font_path.as_ref())?;
+    let shaper_face = rustybuzz::Face::from_slice(&font_data, face_index as u32)
+        .ok_or(PbfFontError::InvalidFontData)?;
+
+    let lib = freetype::Library::init()?;
+    let face = lib.new_face(font_path.as_ref(), face_index)?;
+    let Some(family_name) = face_name(&face) else {
+        return Err(PbfFontError::MissingFontFamilyName);
+    };
+    face.set_char_size(0, (size << 6) as isize, 0, 0)?;
+
+    // Shaper positions are in font units
+    let scale = size as f64 / f64::from(shaper_face.units_per_em());
+    let to_px = |units: i32| (f64::from(units) * scale).round() as i32;
+
+    let mut stack = Fontstack::new();
+    stack.set_name(family_name);
+    let mut code_points: HashMap<GlyphVariant, u32> = HashMap::new();
+    let mut substitutions = HashMap::new();
+    let mut unsupported = Vec::new();
+    let mut seen = HashSet::new();
+
+    for label in labels {
+        let label = label.as_ref();
+        if !seen.insert(label) {
+            continue;
+        }
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(label);
+        buffer.guess_segment_properties();
+        let output = rustybuzz::shape(&shaper_face, &[], buffer);
+        if output.glyph_infos().iter().any(|info| info.glyph_id == 0) {
+            unsupported.push(label.to_string());
+            continue;
+        }
+
+        let mut substitution = String::new();
+        for (info, position) in output.glyph_infos().iter().zip(output.glyph_positions()) {
+            let variant = GlyphVariant {
+                glyph_id: info.glyph_id,
+                x_advance: position.x_advance,
+                x_offset: position.x_offset,
+                y_offset: position.y_offset,
+            };
+
+            let code_point = match code_points.entry(variant) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    let code_point = PRIVATE_USE_AREA_START + stack.glyphs.len() as u32;
+                    if code_point > PRIVATE_USE_AREA_END {
+                        return Err(PbfFontError::PrivateUseAreaExhausted);
+                    }
+
+                    let mut glyph = render_sdf_glyph_from_index(
+                        &face,
+                        variant.glyph_id,
+                        code_point,
+                        3,
+                        radius,
+                        cutoff,
+                    )?;
+                    glyph.set_left(glyph.left() + to_px(variant.x_offset));
+                    glyph.set_top(glyph.top() + to_px(variant.y_offset));
+                    glyph.set_advance(to_px(variant.x_advance).max(0) as u32);
+                    stack.glyphs.push(glyph);
+
+                    *entry.insert(code_point)
+                }
+            };
+            substitution
+                .push(char::from_u32(code_point).expect("Private Use Area code points are valid"));
+        }
+
+        substitutions.insert(label.to_string(), substitution);
+    }
+
+    if let (Some(first), Some(last)) = (stack.glyphs.first(), stack.glyphs.last()) {
+        stack.set_range(format!("{}-{}", first.id(), last.id()));
+    }
+
+    Ok(ShapedFont {
+        stack,
+        substitutions,
+        unsupported,
+    })
+}
@@ -15,6 +15,11 @@ use crate::PbfFontError::MissingFontFamilyName;
 /// See the documentation for [combine_glyphs] for further details.
 /// Unlike [combine_glyphs], the result of this method will always contain a `glyphs` message,
 /// even if the loaded range is empty for a given font.
+///
+/// Fonts are only loaded from pre-rendered PBFs (see [load_glyphs]); missing ranges are never
+/// rendered from source fonts. These loaders are available without the `freetype` feature,
+/// so on-the-fly rendering lives in `get_named_font_stack_or_render` and the
+/// `service::DirectoryStore` fallback instead.
 pub async fn get_named_font_stack<P: AsRef<Path>>(
     font_path: P,
     font_names: &[&str],
@@ -37,21 +42,35 @@ pub async fn get_named_font_stack<P: AsRef<Path>>(
     .filter_map(|g| g.ok())
     .collect();
 
-    // Combine all the glyphs into a single instance, using the ordering to determine priority.
+    combine_font_stack(glyph_data, stack_name, start, end).await
+}
+
+/// Combines the glyphs loaded for each font in a stack, using the ordering to determine
+/// priority. Returns an empty font stack if none of the fonts cover the range.
+pub(crate) async fn combine_font_stack(
+    glyph_data: Vec<Glyphs>,
+    stack_name: String,
+    start: u32,
+    end: u32,
+) -> Result<Glyphs, PbfFontError> {
     // This can take some time, so mark it blocking.
     Ok(spawn_blocking(move || combine_glyphs(glyph_data))
         .await?
-        .unwrap_or_else(|| {
-            // Construct an empty message manually if the range is not covered
-            let mut result = Glyphs::new();
+        .unwrap_or_else(|| empty_glyphs(stack_name, start, end)))
+}
+
+/// Constructs a glyphs message containing a single empty font stack.
+///
+/// This is used when a range is not covered by any of the fonts in a stack.
+pub(crate) fn empty_glyphs(stack_name: String, start: u32, end: u32) -> Glyphs {
+    let mut result = Glyphs::new();
 
-            let mut stack = Fontstack::new();
-            stack.set_name(stack_name);
-            stack.set_range(format!("{start}-{end}"));
+    let mut stack = Fontstack::new();
+    stack.set_name(stack_name);
+    stack.set_range(format!("{start}-{end}"));
 
-            result.stacks.push(stack);
-            result
-        }))
+    result.stacks.push(stack);
+    result
 }
 
 pub async fn get_font_stack<P: AsRef<Path>>(
@@ -67,6 +86,8 @@ pub async fn get_font_stack<P: AsRef<Path>>(
 /// Loads a single font PBF slice from disk.
 ///
 /// Fonts are assumed to be stored in `<font_path>/<font_name>/<start>-<end>.pbf`.
+/// A missing file is an error; with the `freetype` feature, `load_or_render_glyphs`
+/// renders it from the source font instead.
 pub async fn load_glyphs<P: AsRef<Path>>(
     font_path: P,
     font_name: &str,
@@ -80,11 +101,12 @@ pub async fn load_glyphs<P: AsRef<Path>>(
 
     // Note: Counter-intuitively, it's much faster to use blocking IO with `spawn_blocking` here,
     // since the `Message::parse_` call will block as well.
-    Ok(spawn_blocking(|| {
+    spawn_blocking(|| {
+        // Opened separately, so that a missing file is reported as an `IoError`
         let mut file = File::open(full_path)?;
-        Message::parse_from_reader(&mut file)
+        Ok(Message::parse_from_reader(&mut file)?)
     })
-    .await??)
+    .await?
 }
 
 /// Combines a list of SDF font glyphs into a single glyphs message.
@@ -0,0 +1,284 @@
+#!/usr/bin/env python3
+"""Generates arabic_joining.ttf, a tiny TrueType font for the presentation form tests.
+
+Like many modern Arabic fonts, it only provides the joining forms of its letters through GSUB
+features, rather than mapping the presentation form code points. Every glyph is a rectangle of
+a different size, so that the tests can tell them apart.
+
+* Letters: ALEF (U+0627), BEH (U+0628), LAM (U+0644) and TATWEEL (U+0640).
+* `init`, `medi`, `fina` and `isol` give BEH its four joining forms, and ALEF its final form.
+* `calt` replaces the initial BEH, which must not leak into the synthesised presentation forms.
+* `rlig` ligates LAM and the final ALEF, as fonts usually do.
+* BEH FINAL FORM (U+FE90) is mapped directly to a glyph of its own.
+
+Only the standard library is used: python3 arabic_joining.py
+"""
+
+import struct
+from pathlib import Path
+
+GLYPHS = [
+    ".notdef",
+    "alef",
+    "beh",
+    "tatweel",
+    "beh.init",
+    "beh.medi",
+    "beh.fina",
+    "beh.isol",
+    "alef.fina",
+    "uniFE90",
+    "beh.init.calt",
+    "lam",
+    "lam_alef",
+]
+GID = {name: gid for gid, name in enumerate(GLYPHS)}
+
+CMAP = {0x0627: "alef", 0x0628: "beh", 0x0640: "tatweel", 0x0644: "lam", 0xFE90: "uniFE90"}
+
+# Feature tag -> single substitutions (or ligatures, keyed by their components), in tag order
+FEATURES = {
+    "calt": {"beh.init": "beh.init.calt"},
+    "fina": {"beh": "beh.fina", "alef": "alef.fina"},
+    "init": {"beh": "beh.init"},
+    "isol": {"beh": "beh.isol"},
+    "medi": {"beh": "beh.medi"},
+    "rlig": {("lam", "alef.fina"): "lam_alef"},
+}
+
+FAMILY = "Arabic Joining Fixture"
+STYLE = "Regular"
+UNITS_PER_EM = 1000
+
+
+def rectangle(gid):
+    """The bounding box of a glyph's outline; None for the empty .notdef glyph."""
+    if gid == 0:
+        return None
+    return 50, 0, 150 + 40 * gid, 200 + 40 * gid
+
+
+def glyf_and_loca():
+    glyf = b""
+    loca = []
+    for gid in range(len(GLYPHS)):
+        loca.append(len(glyf))
+        box = rectangle(gid)
+        if box is None:
+            continue
+        x_min, y_min, x_max, y_max = box
+        points = [(x_min, y_min), (x_min, y_max), (x_max, y_max), (x_max, y_min)]
+        data = struct.pack(">hhhhh", 1, x_min, y_min, x_max, y_max)
+        data += struct.pack(">HH", 3, 0)  # endPtsOfContours, instructionLength
+        data += bytes([0x01] * 4)  # on curve, long coordinates
+        previous = (0, 0)
+        xs, ys = b"", b""
+        for x, y in points:
+            xs += struct.pack(">h", x - previous[0])
+            ys += struct.pack(">h", y - previous[1])
+            previous = (x, y)
+        data += xs + ys
+        data += b"\0" * (-len(data) % 4)
+        glyf += data
+    loca.append(len(glyf))
+    return glyf, struct.pack(f">{len(loca)}I", *loca)
+
+
+def advance(gid):
+    box = rectangle(gid)
+    return 500 if box is None else box[2] + 50
+
+
+def cmap():
+    segments = [(code, code, (GID[name] - code) % 0x10000) for code, name in sorted(CMAP.items())]
+    segments.append((0xFFFF, 0xFFFF, 1))
+    count = len(segments)
+    search_range = 2 * 2 ** (count.bit_length() - 1)
+    subtable = struct.pack(
+        ">HHHHHHH",
+        4,
+        16 + 8 * count,
+        0,
+        2 * count,
+        search_range,
+        (count.bit_length() - 1),
+        2 * count - search_range,
+    )
+    subtable += struct.pack(f">{count}H", *(end for _, end, _ in segments))
+    subtable += struct.pack(">H", 0)
+    subtable += struct.pack(f">{count}H", *(start for start, _, _ in segments))
+    subtable += struct.pack(f">{count}H", *(delta for _, _, delta in segments))
+    subtable += struct.pack(f">{count}H", *([0] * count))
+    return struct.pack(">HHHHI", 0, 1, 3, 1, 12) + subtable
+
+
+def name():
+    strings = [(1, FAMILY), (2, STYLE), (4, f"{FAMILY} {STYLE}"), (6, "ArabicJoiningFixture")]
+    records, storage = b"", b""
+    for name_id, value in strings:
+        encoded = value.encode("utf-16-be")
+        records += struct.pack(">HHHHHH", 3, 1, 0x409, name_id, len(encoded), len(storage))
+        storage += encoded
+    return struct.pack(">HHH", 0, len(strings), 6 + len(records)) + records + storage
+
+
+def coverage(glyphs):
+    return struct.pack(f">HH{len(glyphs)}H", 1, len(glyphs), *glyphs)
+
+
+def single_substitution(substitutions):
+    pairs = sorted((GID[source], GID[target]) for source, target in substitutions.items())
+    count = len(pairs)
+    subtable = struct.pack(f">HHH{count}H", 2, 6 + 2 * count, count, *(t for _, t in pairs))
+    return subtable + coverage([s for s, _ in pairs])
+
+
+def ligature_substitution(ligatures):
+    """A ligature substitution subtable for ligatures which all start with different glyphs."""
+    entries = sorted(
+        (GID[components[0]], [GID[c] for c in components[1:]], GID[ligature])
+        for components, ligature in ligatures.items()
+    )
+    count = len(entries)
+    sets = b""
+    set_offsets = []
+    for _, rest, ligature in entries:
+        set_offsets.append(6 + 2 * count + len(sets))
+        # A ligature set with a single ligature
+        sets += struct.pack(f">HHHH{len(rest)}H", 1, 4, ligature, len(rest) + 1, *rest)
+    subtable = struct.pack(f">HHH{count}H", 1, 6 + 2 * count + len(sets), count, *set_offsets)
+    return subtable + sets + coverage([first for first, _, _ in entries])
+
+
+def lookup(substitutions):
+    if all(isinstance(source, tuple) for source in substitutions):
+        return struct.pack(">HHHH", 4, 0, 1, 8) + ligature_substitution(substitutions)
+    return struct.pack(">HHHH", 1, 0, 1, 8) + single_substitution(substitutions)
+
+
+def gsub():
+    tags = list(FEATURES)
+
+    lang_sys = struct.pack(f">HHH{len(tags)}H", 0, 0xFFFF, len(tags), *range(len(tags)))
+    script = struct.pack(">HH", 4, 0) + lang_sys
+    script_list = struct.pack(">H4sH", 1, b"arab", 8) + script
+
+    feature_list = struct.pack(">H", len(tags))
+    features = b""
+    for index, tag in enumerate(tags):
+        offset = 2 + 6 * len(tags) + len(features)
+        feature_list += struct.pack(">4sH", tag.encode(), offset)
+        features += struct.pack(">HHH", 0, 1, index)
+    feature_list += features
+
+    lookup_list = struct.pack(">H", len(tags))
+    lookups = b""
+    for tag in tags:
+        offset = 2 + 2 * len(tags) + len(lookups)
+        lookup_list += struct.pack(">H", offset)
+        lookups += lookup(FEATURES[tag])
+    lookup_list += lookups
+
+    header_size = 10
+    return (
+        struct.pack(
+            ">HHHHH",
+            1,
+            0,
+            header_size,
+            header_size + len(script_list),
+            header_size + len(script_list) + len(feature_list),
+        )
+        + script_list
+        + feature_list
+        + lookup_list
+    )
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(f">{len(data) // 4}I", data)) & 0xFFFFFFFF
+
+
+def build():
+    glyf, loca = glyf_and_loca()
+    boxes = [box for box in map(rectangle, range(len(GLYPHS))) if box]
+    x_max = max(box[2] for box in boxes)
+    y_max = max(box[3] for box in boxes)
+    advances = [advance(gid) for gid in range(len(GLYPHS))]
+
+    tables = {
+        "GSUB": gsub(),
+        "cmap": cmap(),
+        "glyf": glyf,
+        "head": struct.pack(
+            ">IIIIHHqqhhhhHHhhh",
+            0x00010000,
+            0x00010000,
+            0,
+            0x5F0F3CF5,
+            3,
+            UNITS_PER_EM,
+            0,
+            0,
+            50,
+            0,
+            x_max,
+            y_max,
+            0,
+            8,
+            2,
+            1,
+            0,
+        ),
+        "hhea": struct.pack(
+            ">IhhhHhhhhhhhhhhhH",
+            0x00010000,
+            800,
+            -200,
+            0,
+            max(advances),
+            0,
+            0,
+            x_max,
+            1,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            len(GLYPHS),
+        ),
+        "hmtx": b"".join(
+            struct.pack(">Hh", advance(gid), 0 if rectangle(gid) is None else 50)
+            for gid in range(len(GLYPHS))
+        ),
+        "loca": loca,
+        "maxp": struct.pack(">IHHHHHHHHHHHHHH", 0x00010000, len(GLYPHS), 4, 1, 0, 0, 2, *[0] * 8),
+        "name": name(),
+        "post": struct.pack(">IIhhIIIII", 0x00030000, 0, -100, 50, 0, 0, 0, 0, 0),
+    }
+
+    count = len(tables)
+    entry_selector = count.bit_length() - 1
+    search_range = 16 * 2**entry_selector
+    font = struct.pack(">IHHHH", 0x00010000, count, search_range, entry_selector, 16 * count - search_range)
+    offset = 12 + 16 * count
+    directory, data = b"", b""
+    for tag in sorted(tables):
+        table = tables[tag]
+        directory += struct.pack(">4sIII", tag.encode(), checksum(table), offset + len(data), len(table))
+        data += table + b"\0" * (-len(table) % 4)
+    font += directory + data
+
+    # The head table's checkSumAdjustment makes the whole font sum to a magic number
+    head_offset = font.index(b"head") + 8
+    head_offset = struct.unpack(">I", font[head_offset : head_offset + 4])[0]
+    adjustment = (0xB1B0AFBA - checksum(font)) & 0xFFFFFFFF
+    return font[: head_offset + 8] + struct.pack(">I", adjustment) + font[head_offset + 12 :]
+
+
+if __name__ == "__main__":
+    Path(__file__).with_suffix(".ttf").write_bytes(build())
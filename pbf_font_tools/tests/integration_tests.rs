@@ -5,6 +5,27 @@ use std::path::Path;
 
 use futures::future::join3;
 
+/// The font most of the rendering tests use, which is also the name of its fixture directory.
+#[cfg(feature = "freetype")]
+const OPEN_SANS_LIGHT: &str = "Open Sans Light";
+
+#[cfg(feature = "freetype")]
+fn open_sans_light_path() -> std::path::PathBuf {
+    Path::new("tests")
+        .join("glyphs")
+        .join(OPEN_SANS_LIGHT)
+        .join(format!("{OPEN_SANS_LIGHT}.ttf"))
+}
+
+/// Loads the Open Sans Light fixture font (the face keeps its FreeType library alive).
+#[cfg(feature = "freetype")]
+fn open_sans_light() -> pbf_font_tools::freetype::Face {
+    pbf_font_tools::freetype::Library::init()
+        .expect("Unable to initialize FreeType")
+        .new_face(open_sans_light_path(), 0)
+        .expect("Unable to load font")
+}
+
 #[tokio::test]
 async fn test_load_glyphs() {
     let font_path = Path::new("tests").join("glyphs");
@@ -142,3 +163,681 @@ async fn test_glyph_generation() {
         .zip(fixture_stack.glyphs.iter())
         .for_each(|(glyph, fixture)| assert_eq!(glyph, fixture));
 }
+
+#[cfg(feature = "freetype")]
+#[test]
+fn test_lossy_glyph_generation() {
+    let face = open_sans_light();
+
+    let stack = pbf_font_tools::glyph_range_for_face(&face, 0, 255, 24, 8, 0.25)
+        .expect("Unable to render glyphs");
+    let (lossy_stack, errors) =
+        pbf_font_tools::glyph_range_for_face_lossy(&face, 0, 255, 24, 8, 0.25)
+            .expect("Unable to render glyphs");
+    assert_eq!(lossy_stack, stack);
+    assert!(errors.is_empty());
+
+    // An invalid cutoff makes every glyph fail, which should be reported per glyph.
+    assert!(pbf_font_tools::glyph_range_for_face(&face, 0, 255, 24, 8, 1.5).is_err());
+    let (lossy_stack, errors) =
+        pbf_font_tools::glyph_range_for_face_lossy(&face, 0, 255, 24, 8, 1.5)
+            .expect("Unable to render glyphs");
+    assert!(lossy_stack.glyphs.is_empty());
+    assert_eq!(errors.len(), stack.glyphs.len());
+    assert_eq!(errors[0].0, stack.glyphs[0].id());
+}
+
+#[cfg(feature = "freetype")]
+#[tokio::test]
+async fn test_parallel_glyph_generation() {
+    let otf_path = open_sans_light_path();
+    let sequential_glyphs = pbf_font_tools::glyph_range_for_font(&otf_path, 0, 255, 24, 8, 0.25)
+        .expect("Unable to render glyphs");
+
+    for num_threads in [0, 1, 3] {
+        let parallel_glyphs = pbf_font_tools::glyph_range_for_font_parallel(
+            &otf_path,
+            0,
+            255,
+            24,
+            8,
+            0.25,
+            num_threads,
+        )
+        .expect("Unable to render glyphs in parallel");
+        assert_eq!(parallel_glyphs, sequential_glyphs);
+    }
+
+    let async_glyphs = pbf_font_tools::glyph_range_for_font_async(&otf_path, 0, 255, 24, 8, 0.25)
+        .await
+        .expect("Unable to render glyphs asynchronously");
+    assert_eq!(async_glyphs, sequential_glyphs);
+}
+
+#[cfg(feature = "freetype")]
+#[tokio::test]
+async fn test_parallel_glyph_generation_with_options() {
+    let otf_path = open_sans_light_path();
+    let face = open_sans_light();
+
+    let options = pbf_font_tools::RenderOptions::new()
+        .with_size(20.5)
+        .with_trim(true)
+        .with_embolden(0.5)
+        .with_rounding(pbf_font_tools::Rounding::Nearest);
+    let stack = pbf_font_tools::glyph_range_for_face_with_options(&face, 0, 255, &options)
+        .expect("Unable to render glyphs");
+
+    let parallel_glyphs =
+        pbf_font_tools::glyph_range_for_font_parallel_with_options(&otf_path, 0, 255, &options, 3)
+            .expect("Unable to render glyphs in parallel");
+    assert_eq!(parallel_glyphs.stacks, vec![stack.clone()]);
+
+    let async_glyphs =
+        pbf_font_tools::glyph_range_for_font_async_with_options(&otf_path, 0, 255, &options)
+            .await
+            .expect("Unable to render glyphs asynchronously");
+    assert_eq!(async_glyphs.stacks, vec![stack.clone()]);
+
+    // In lossy mode, every glyph failing is reported with its face and code point.
+    let options = options.with_render_settings(24, 8, 1.5);
+    let (parallel_glyphs, errors) =
+        pbf_font_tools::glyph_range_for_font_parallel_lossy_with_options(
+            &otf_path, 0, 255, &options, 3,
+        )
+        .expect("Unable to render glyphs in parallel");
+    assert!(parallel_glyphs.stacks[0].glyphs.is_empty());
+    assert_eq!(errors.len(), stack.glyphs.len());
+    assert_eq!((errors[0].0, errors[0].1), (0, stack.glyphs[0].id()));
+
+    let (async_glyphs, async_errors) =
+        pbf_font_tools::glyph_range_for_font_async_lossy_with_options(&otf_path, 0, 255, &options)
+            .await
+            .expect("Unable to render glyphs asynchronously");
+    assert_eq!(async_glyphs, parallel_glyphs);
+    assert_eq!(async_errors.len(), errors.len());
+}
+
+#[cfg(feature = "freetype")]
+#[tokio::test]
+async fn test_fallback_rendering() {
+    let font_path = Path::new("tests").join("glyphs");
+    let font_name = OPEN_SANS_LIGHT;
+    let out_dir =
+        std::env::temp_dir().join(format!("pbf_font_tools_fallback_{}", std::process::id()));
+
+    let fallback = pbf_font_tools::FallbackRenderer::new()
+        .with_font_dir(font_path.join(font_name))
+        .expect("Unable to scan fonts")
+        .with_write_back(true);
+    assert!(fallback.source(font_name).is_some());
+
+    let rendered_glyphs = pbf_font_tools::load_or_render_glyphs(
+        &out_dir,
+        font_name,
+        0,
+        255,
+        std::sync::Arc::new(fallback),
+    )
+    .await
+    .expect("Unable to render glyphs");
+    let written_glyphs = pbf_font_tools::load_glyphs(&out_dir, font_name, 0, 255)
+        .await
+        .expect("Rendered glyphs were not written back");
+    let fixture_glyphs = pbf_font_tools::load_glyphs(font_path.as_path(), font_name, 0, 255)
+        .await
+        .expect("Unable to load fixtures");
+    std::fs::remove_dir_all(&out_dir).expect("Unable to clean up");
+
+    assert_eq!(rendered_glyphs, written_glyphs);
+    assert_eq!(
+        rendered_glyphs.stacks[0].name,
+        Some(String::from(font_name))
+    );
+    assert_eq!(
+        rendered_glyphs.stacks[0].glyphs,
+        fixture_glyphs.stacks[0].glyphs
+    );
+}
+
+#[cfg(feature = "freetype")]
+#[tokio::test]
+async fn test_fallback_font_stack() {
+    let font_path = Path::new("tests").join("glyphs");
+    let empty_dir = std::env::temp_dir().join(format!(
+        "pbf_font_tools_fallback_stack_{}",
+        std::process::id()
+    ));
+    let options = pbf_font_tools::RenderOptions::new()
+        .with_trim(true)
+        .with_embolden(0.02)
+        .with_rounding(pbf_font_tools::Rounding::Nearest);
+
+    let fallback = pbf_font_tools::FallbackRenderer::new()
+        .with_font_dir(font_path.join(OPEN_SANS_LIGHT))
+        .expect("Unable to scan fonts")
+        .with_render_options(options.clone());
+    let glyphs = pbf_font_tools::get_font_stack_or_render(
+        &empty_dir,
+        &[OPEN_SANS_LIGHT],
+        0,
+        255,
+        std::sync::Arc::new(fallback),
+    )
+    .await
+    .expect("Unable to render font stack");
+
+    // The on-the-fly range matches one rendered ahead of time with the same options
+    let expected =
+        pbf_font_tools::glyph_range_for_face_with_options(&open_sans_light(), 0, 255, &options)
+            .expect("Unable to render glyphs");
+    assert_eq!(glyphs.stacks.len(), 1);
+    assert_eq!(glyphs.stacks[0].name, Some(String::from(OPEN_SANS_LIGHT)));
+    assert_eq!(glyphs.stacks[0].glyphs, expected.glyphs);
+
+    // Without a fallback, the range can't be loaded
+    let glyphs = pbf_font_tools::get_font_stack(&empty_dir, &[OPEN_SANS_LIGHT], 0, 255)
+        .await
+        .expect("Unable to load font stack");
+    assert!(glyphs.stacks[0].glyphs.is_empty());
+}
+
+#[cfg(feature = "freetype")]
+#[test]
+fn test_fallback_skips_broken_fonts() {
+    let font_dir =
+        std::env::temp_dir().join(format!("pbf_font_tools_broken_{}", std::process::id()));
+    std::fs::create_dir_all(&font_dir).expect("Unable to create font dir");
+    std::fs::copy(
+        open_sans_light_path(),
+        font_dir.join(format!("{OPEN_SANS_LIGHT}.ttf")),
+    )
+    .expect("Unable to copy font");
+    std::fs::write(font_dir.join("Broken.ttf"), b"not a font").expect("Unable to write font");
+
+    let result = pbf_font_tools::FallbackRenderer::new().with_font_dir_lossy(&font_dir);
+    std::fs::remove_dir_all(&font_dir).expect("Unable to clean up");
+
+    let (fallback, skipped) = result.expect("Unable to scan fonts");
+    assert!(fallback.source(OPEN_SANS_LIGHT).is_some());
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].0, font_dir.join("Broken.ttf"));
+}
+
+#[cfg(feature = "axum")]
+mod service {
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+
+    use axum::body::{to_bytes, Body, Bytes};
+    use axum::http::{Request, StatusCode};
+    use axum::Router;
+    use pbf_font_tools::protobuf::Message;
+    use pbf_font_tools::service::{parse_range, DirectoryStore, GlyphCache, GlyphService};
+    use pbf_font_tools::Glyphs;
+    use tower::ServiceExt;
+
+    /// A simple cache which records how many times it was hit.
+    #[derive(Clone, Default)]
+    struct CountingCache {
+        entries: Arc<Mutex<HashMap<String, Bytes>>>,
+        hits: Arc<Mutex<usize>>,
+    }
+
+    impl GlyphCache for CountingCache {
+        fn get(&self, key: &str) -> Option<Bytes> {
+            let entry = self.entries.lock().unwrap().get(key).cloned();
+            if entry.is_some() {
+                *self.hits.lock().unwrap() += 1;
+            }
+            entry
+        }
+
+        fn insert(&self, key: &str, data: Bytes) {
+            self.entries.lock().unwrap().insert(key.to_string(), data);
+        }
+    }
+
+    fn router() -> Router {
+        GlyphService::new(DirectoryStore::new(Path::new("tests").join("glyphs"))).into_router()
+    }
+
+    async fn get(router: Router, uri: &str) -> (StatusCode, Bytes) {
+        let response = router
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        (
+            status,
+            to_bytes(response.into_body(), usize::MAX).await.unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("0-255.pbf"), Some((0, 255)));
+        assert_eq!(parse_range("65280-65535.pbf"), Some((65280, 65535)));
+        assert_eq!(parse_range("1-256.pbf"), None);
+        assert_eq!(parse_range("0-511.pbf"), None);
+        assert_eq!(parse_range("65536-65791.pbf"), None);
+        assert_eq!(parse_range("0-255"), None);
+    }
+
+    #[tokio::test]
+    async fn test_url_encoded_font_stack() {
+        let (status, body) = get(router(), "/SeoulNamsan%20L,Open%20Sans%20Light/0-255.pbf").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let glyphs = Glyphs::parse_from_bytes(&body).unwrap();
+        let stack = &glyphs.stacks[0];
+        assert_eq!(
+            stack.name,
+            Some(String::from("SeoulNamsan L, Open Sans Light"))
+        );
+        assert_eq!(stack.glyphs.len(), 228);
+    }
+
+    #[tokio::test]
+    async fn test_empty_range() {
+        let (status, body) = get(router(), "/Open%20Sans%20Light/65280-65535.pbf").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let glyphs = Glyphs::parse_from_bytes(&body).unwrap();
+        assert_eq!(glyphs.stacks[0].range, Some(String::from("65280-65535")));
+        assert!(glyphs.stacks[0].glyphs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_error_statuses() {
+        let (status, _) = get(router(), "/Open%20Sans%20Light/128-383.pbf").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = get(router(), "/Open%20Sans%20Light/0-255.png").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = get(router(), "/Comic%20Sans,Papyrus/0-255.pbf").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_injected_cache() {
+        let cache = CountingCache::default();
+        let router = GlyphService::new(DirectoryStore::new(Path::new("tests").join("glyphs")))
+            .with_cache(cache.clone())
+            .into_router();
+
+        let (_, first) = get(router.clone(), "/Open%20Sans%20Light/0-255.pbf").await;
+        let (_, second) = get(router, "/Open%20Sans%20Light/0-255.pbf").await;
+
+        assert_eq!(first, second);
+        assert_eq!(*cache.hits.lock().unwrap(), 1);
+        assert!(cache
+            .entries
+            .lock()
+            .unwrap()
+            .contains_key("Open Sans Light/0-255"));
+    }
+
+    #[tokio::test]
+    async fn test_store_errors_are_not_cached() {
+        let glyph_dir =
+            std::env::temp_dir().join(format!("pbf_font_tools_corrupt_{}", std::process::id()));
+        let font_dir = glyph_dir.join("Corrupt");
+        std::fs::create_dir_all(&font_dir).expect("Unable to create font dir");
+        std::fs::write(font_dir.join("0-255.pbf"), b"\xff\xff\xff").expect("Unable to write");
+
+        let cache = CountingCache::default();
+        let router = GlyphService::new(DirectoryStore::new(&glyph_dir))
+            .with_cache(cache.clone())
+            .into_router();
+
+        // A corrupt range is an error, but a missing one is not
+        let (corrupt, _) = get(router.clone(), "/Corrupt/0-255.pbf").await;
+        let (missing, _) = get(router, "/Corrupt/256-511.pbf").await;
+        std::fs::remove_dir_all(&glyph_dir).expect("Unable to clean up");
+
+        assert_eq!(corrupt, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(missing, StatusCode::OK);
+        assert!(!cache.entries.lock().unwrap().contains_key("Corrupt/0-255"));
+    }
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn test_shape_labels() {
+    let font_path = open_sans_light_path();
+    let labels = ["Hello", "AVA", "Hello", "Tofu", "東京", "Tofu 東京"];
+
+    let shaped = pbf_font_tools::shape_labels(&font_path, 0, &labels, 24, 8, 0.25)
+        .expect("Unable to shape labels");
+    assert_eq!(shaped.stack.name, Some(String::from(OPEN_SANS_LIGHT)));
+    assert_eq!(shaped.substitutions.len(), 3);
+
+    // Labels the font can't cover are left for another font, without rendering any tofu
+    assert_eq!(shaped.unsupported, vec!["東京", "Tofu 東京"]);
+    let supported_only = pbf_font_tools::shape_labels(&font_path, 0, &labels[..4], 24, 8, 0.25)
+        .expect("Unable to shape labels");
+    assert_eq!(supported_only.stack, shaped.stack);
+
+    // Every glyph used by a label is in the stack, in the Private Use Area
+    let ids: Vec<u32> = shaped.stack.glyphs.iter().map(|glyph| glyph.id()).collect();
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    for (label, substitution) in &shaped.substitutions {
+        assert_eq!(substitution.chars().count(), label.chars().count());
+        for c in substitution.chars() {
+            assert!((pbf_font_tools::PRIVATE_USE_AREA_START
+                ..=pbf_font_tools::PRIVATE_USE_AREA_END)
+                .contains(&(c as u32)));
+            assert!(ids.contains(&(c as u32)));
+        }
+    }
+
+    // Identically positioned glyphs are shared (the two "l"s in "Hello")
+    let hello: Vec<char> = shaped.substitutions["Hello"].chars().collect();
+    assert_eq!(hello[2], hello[3]);
+
+    // Unkerned glyphs keep the advance they would have when rendered by code point
+    let unshaped = pbf_font_tools::glyph_range_for_font(&font_path, 0, 255, 24, 8, 0.25)
+        .expect("Unable to render glyphs");
+    let find = |glyphs: &[pbf_font_tools::Glyph], id: u32| {
+        glyphs
+            .iter()
+            .find(|glyph| glyph.id() == id)
+            .cloned()
+            .unwrap()
+    };
+    let l = find(&unshaped.stacks[0].glyphs, 'l' as u32);
+    let shaped_l = find(&shaped.stack.glyphs, hello[2] as u32);
+    assert_eq!(shaped_l.bitmap, l.bitmap);
+    assert_eq!(shaped_l.advance(), l.advance());
+
+    let ranges = shaped.ranges();
+    assert_eq!(ranges.len(), 1);
+    assert_eq!((ranges[0].0, ranges[0].1), (0xE000, 0xE0FF));
+    assert_eq!(ranges[0].2.stacks[0].glyphs, shaped.stack.glyphs);
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn test_presentation_forms_without_arabic() {
+    let face = open_sans_light();
+
+    // Nothing is synthesized for a font without any Arabic letters
+    for (start, end) in [(64256, 64511), (65024, 65279)] {
+        let stack = pbf_font_tools::glyph_range_for_face(&face, start, end, 24, 8, 0.25)
+            .expect("Unable to render glyphs");
+        assert!(stack
+            .glyphs
+            .iter()
+            .all(|glyph| face.get_char_index(glyph.id() as usize).is_ok()));
+    }
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn test_presentation_forms() {
+    // See tests/fixtures/arabic_joining.py for the contents of this font
+    let font_path = Path::new("tests")
+        .join("fixtures")
+        .join("arabic_joining.ttf");
+    let face = pbf_font_tools::freetype::Library::init()
+        .expect("Unable to initialize FreeType")
+        .new_face(font_path, 0)
+        .expect("Unable to load font");
+
+    let stack = pbf_font_tools::glyph_range_for_face(&face, 0xFE70, 0xFEFF, 24, 8, 0.25)
+        .expect("Unable to render glyphs");
+    let from_index = |glyph_index: u32, id: u32| {
+        pbf_font_tools::render_sdf_glyph_from_index(&face, glyph_index, id, 3, 8, 0.25)
+            .expect("Unable to render glyph")
+    };
+
+    // Letters get the glyphs of the font's own joining features (but not `calt`), except for
+    // the final BEH, which the font maps directly. Ligature forms also use required ligatures.
+    let expected: Vec<_> = [
+        (0xFE8D, 1),  // ALEF ISOLATED FORM: the letter itself
+        (0xFE8E, 8),  // ALEF FINAL FORM: fina
+        (0xFE8F, 7),  // BEH ISOLATED FORM: isol
+        (0xFE90, 9),  // BEH FINAL FORM: the font's own glyph
+        (0xFE91, 4),  // BEH INITIAL FORM: init
+        (0xFE92, 5),  // BEH MEDIAL FORM: medi
+        (0xFEDD, 11), // LAM (in every form): the letter itself
+        (0xFEDE, 11),
+        (0xFEDF, 11),
+        (0xFEE0, 11),
+        (0xFEFB, 12), // LAM WITH ALEF ISOLATED FORM: rlig
+        (0xFEFC, 12), // LAM WITH ALEF FINAL FORM: rlig
+    ]
+    .into_iter()
+    .map(|(id, glyph_index)| from_index(glyph_index, id))
+    .collect();
+    assert_eq!(stack.glyphs, expected);
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn test_glyph_generation_with_features() {
+    use std::str::FromStr;
+
+    use pbf_font_tools::rustybuzz::{Feature, Language};
+    use pbf_font_tools::RenderOptions;
+
+    let face = open_sans_light();
+
+    let stack = pbf_font_tools::glyph_range_for_face(&face, 0, 255, 24, 8, 0.25)
+        .expect("Unable to render glyphs");
+    let options = RenderOptions::new().with_name(String::from("Open Sans Light Oldstyle"));
+    assert_eq!(
+        pbf_font_tools::glyph_range_for_face_with_options(&face, 0, 255, &options)
+            .expect("Unable to render glyphs")
+            .glyphs,
+        stack.glyphs
+    );
+
+    let options = options.with_features([Feature::from_str("onum").unwrap()]);
+    let oldstyle = pbf_font_tools::glyph_range_for_face_with_options(&face, 0, 255, &options)
+        .expect("Unable to render glyphs");
+    assert_eq!(oldstyle.name(), "Open Sans Light Oldstyle");
+    assert_eq!(oldstyle.range(), "0-255");
+    assert_eq!(oldstyle.glyphs.len(), stack.glyphs.len());
+
+    // Only the digits are replaced, keeping their code points
+    let replaced = |stack: &pbf_font_tools::Fontstack, variant: &pbf_font_tools::Fontstack| {
+        assert_eq!(stack.glyphs.len(), variant.glyphs.len());
+        stack
+            .glyphs
+            .iter()
+            .zip(&variant.glyphs)
+            .filter(|(glyph, variant_glyph)| {
+                assert_eq!(glyph.id(), variant_glyph.id());
+                glyph != variant_glyph
+            })
+            .map(|(glyph, _)| char::from_u32(glyph.id()).unwrap())
+            .collect::<Vec<_>>()
+    };
+    let digits = replaced(&stack, &oldstyle);
+    assert!(!digits.is_empty());
+    assert!(digits.iter().all(char::is_ascii_digit));
+
+    // The font has Romanian forms of the letters with a comma below
+    let stack = pbf_font_tools::glyph_range_for_face(&face, 256, 767, 24, 8, 0.25)
+        .expect("Unable to render glyphs");
+    let options = RenderOptions::new().with_language(Language::from_str("ro").unwrap());
+    let romanian = pbf_font_tools::glyph_range_for_face_with_options(&face, 256, 767, &options)
+        .expect("Unable to render glyphs");
+    assert_eq!(replaced(&stack, &romanian), vec!['Ș', 'ș', 'Ț', 'ț']);
+}
+
+#[cfg(feature = "freetype")]
+#[test]
+fn test_synthetic_styles() {
+    use pbf_font_tools::RenderOptions;
+
+    let face = open_sans_light();
+
+    let stack = pbf_font_tools::glyph_range_for_face(&face, 0, 255, 24, 8, 0.25)
+        .expect("Unable to render glyphs");
+    let bold = pbf_font_tools::glyph_range_for_face_with_options(
+        &face,
+        0,
+        255,
+        &RenderOptions::new().with_embolden(1.0 / 24.0),
+    )
+    .expect("Unable to render glyphs");
+    assert_eq!(bold.name(), "Open Sans Light Bold (synthetic)");
+    assert_eq!(bold.glyphs.len(), stack.glyphs.len());
+    for (glyph, bold_glyph) in stack.glyphs.iter().zip(&bold.glyphs) {
+        assert_eq!(glyph.id(), bold_glyph.id());
+        assert_eq!(bold_glyph.advance(), glyph.advance() + 1);
+    }
+
+    let options = RenderOptions::new().with_embolden(0.05).with_oblique(0.2);
+    let bold_oblique = pbf_font_tools::glyph_range_for_face_with_options(&face, 0, 255, &options)
+        .expect("Unable to render glyphs");
+    assert_eq!(
+        options.stack_name(&face).as_deref(),
+        Some("Open Sans Light Bold Oblique (synthetic)")
+    );
+    assert_eq!(
+        bold_oblique.name(),
+        "Open Sans Light Bold Oblique (synthetic)"
+    );
+
+    // An explicit name takes precedence
+    let options = options.with_name(String::from("Open Sans Semibold Italic"));
+    assert_eq!(
+        options.stack_name(&face).as_deref(),
+        Some("Open Sans Semibold Italic")
+    );
+}
+
+#[cfg(feature = "freetype")]
+#[test]
+fn test_render_glyph_from_index() {
+    let face = open_sans_light();
+    face.set_char_size(0, 24 << 6, 0, 0)
+        .expect("Unable to set font size");
+
+    let glyph_index = face.get_char_index('&' as usize).unwrap().get();
+    let glyph = pbf_font_tools::render_sdf_glyph_from_index(&face, glyph_index, 0xE000, 3, 8, 0.25)
+        .expect("Unable to render glyph");
+    let mut expected = pbf_font_tools::render_sdf_glyph(&face, '&' as u32, 3, 8, 0.25)
+        .expect("Unable to render glyph");
+    expected.set_id(0xE000);
+    assert_eq!(glyph, expected);
+
+    assert!(matches!(
+        pbf_font_tools::render_sdf_glyph(&face, 0x4E00, 3, 8, 0.25),
+        Err(pbf_font_tools::PbfFontError::SdfGlyphError(
+            sdf_glyph_renderer::SdfGlyphError::MissingGlyph(0x4E00)
+        ))
+    ));
+}
+
+#[cfg(feature = "freetype")]
+#[test]
+fn test_bitmap_font_generation() {
+    let font_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("sdf_glyph_renderer")
+        .join("fixtures")
+        .join("bitmap_font.bdf");
+    let lib = pbf_font_tools::freetype::Library::init().expect("Unable to initialize FreeType");
+    let face = lib.new_face(&font_path, 0).expect("Unable to load font");
+
+    // The font only has a 16px strike, which is scaled to 24px
+    let stack = pbf_font_tools::glyph_range_for_face(&face, 0, 255, 24, 8, 0.25)
+        .expect("Unable to render glyphs");
+    assert_eq!(stack.name(), "Bitmap Fixture Regular");
+    let ids: Vec<u32> = stack.glyphs.iter().map(|glyph| glyph.id()).collect();
+    assert_eq!(ids, vec![' ' as u32, '#' as u32, '_' as u32]);
+
+    let square = &stack.glyphs[1];
+    assert_eq!((square.width(), square.height()), (15, 15));
+    assert_eq!(square.advance(), 18);
+    assert_eq!(square.bitmap().len(), 21 * 21);
+}
+
+#[cfg(feature = "freetype")]
+#[test]
+fn test_fractional_sizes_and_rounding() {
+    use pbf_font_tools::{RenderOptions, Rounding};
+
+    let face = open_sans_light();
+    let render = |options: RenderOptions| {
+        pbf_font_tools::glyph_range_for_face_with_options(&face, 0, 255, &options)
+            .expect("Unable to render glyphs")
+    };
+    let advances = |stack: &pbf_font_tools::Fontstack| -> Vec<u32> {
+        stack.glyphs.iter().map(|glyph| glyph.advance()).collect()
+    };
+
+    // The defaults match the plain renderer, and the resolution scales the size
+    let default = render(RenderOptions::new());
+    assert_eq!(
+        default,
+        pbf_font_tools::glyph_range_for_face(&face, 0, 255, 24, 8, 0.25)
+            .expect("Unable to render glyphs")
+    );
+    assert_eq!(
+        render(RenderOptions::new().with_size(12.0).with_dpi(144)),
+        default
+    );
+
+    // Rounding to the nearest pixel only ever rounds up where the default rounds down
+    let nearest = render(RenderOptions::new().with_rounding(Rounding::Nearest));
+    let differences: Vec<u32> = advances(&nearest)
+        .iter()
+        .zip(advances(&default))
+        .map(|(nearest, default)| nearest - default)
+        .collect();
+    assert!(differences.iter().all(|difference| *difference <= 1));
+    assert!(differences.contains(&0) && differences.contains(&1));
+
+    // A fractional size falls between its neighbours
+    let total = |size: f64| -> u32 {
+        let stack = render(
+            RenderOptions::new()
+                .with_size(size)
+                .with_rounding(Rounding::Nearest),
+        );
+        advances(&stack).iter().sum()
+    };
+    let (smaller, fractional, larger) = (total(24.0), total(24.5), total(25.0));
+    assert!(smaller < fractional && fractional < larger);
+}
+
+#[cfg(feature = "freetype")]
+#[test]
+fn test_trimmed_glyphs() {
+    use pbf_font_tools::RenderOptions;
+
+    let face = open_sans_light();
+    let render = |options: RenderOptions| {
+        pbf_font_tools::glyph_range_for_face_with_options(&face, 0, 255, &options)
+            .expect("Unable to render glyphs")
+    };
+    let untrimmed = render(RenderOptions::new());
+    let trimmed = render(RenderOptions::new().with_trim(true));
+    assert_eq!(untrimmed.glyphs.len(), trimmed.glyphs.len());
+
+    for (untrimmed, trimmed) in untrimmed.glyphs.iter().zip(&trimmed.glyphs) {
+        assert_eq!(untrimmed.id(), trimmed.id());
+        assert_eq!(untrimmed.advance(), trimmed.advance());
+        assert!(trimmed.width() <= untrimmed.width());
+        assert!(trimmed.height() <= untrimmed.height());
+        assert!(trimmed.bitmap().len() <= untrimmed.bitmap().len());
+    }
+
+    // Blank glyphs keep their advance, but have no bitmap
+    let space = trimmed
+        .glyphs
+        .iter()
+        .find(|glyph| glyph.id() == ' ' as u32)
+        .expect("Space is missing");
+    assert!(!space.has_bitmap());
+    assert_eq!((space.width(), space.height()), (0, 0));
+    assert!(space.advance() > 0);
+}
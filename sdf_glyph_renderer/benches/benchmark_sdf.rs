@@ -1,5 +1,5 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use sdf_glyph_renderer::BitmapGlyph;
+use sdf_glyph_renderer::{BitmapGlyph, DistanceAlgorithm, SdfRenderer};
 use std::hint::black_box;
 
 pub fn benchmark_sdf(c: &mut Criterion) {
@@ -8,7 +8,70 @@ pub fn benchmark_sdf(c: &mut Criterion) {
         let bitmap = black_box(BitmapGlyph::new(alpha, 16, 19, 3).unwrap());
         b.iter(|| bitmap.render_sdf(8))
     });
+
+    c.bench_function("benchmark f32 sdf gen", |b| {
+        let alpha = Vec::from(include!("../fixtures/glyph_alpha.json"));
+        let bitmap = black_box(BitmapGlyph::new(alpha, 16, 19, 3).unwrap());
+        b.iter(|| bitmap.render_sdf_f32(8))
+    });
+
+    c.bench_function("benchmark sdf gen with reused renderer", |b| {
+        let alpha = Vec::from(include!("../fixtures/glyph_alpha.json"));
+        let bitmap = black_box(BitmapGlyph::new(alpha, 16, 19, 3).unwrap());
+        let mut renderer = SdfRenderer::new();
+        let mut output = vec![0f64; (16 + 6) * (19 + 6)];
+        b.iter(|| renderer.render_sdf_into(&bitmap, 8, &mut output))
+    });
+}
+
+/// Compares the distance transform algorithms on a standard glyph.
+pub fn benchmark_algorithms(c: &mut Criterion) {
+    let alpha = Vec::from(include!("../fixtures/glyph_alpha.json"));
+    let bitmap = black_box(BitmapGlyph::new(alpha, 16, 19, 3).unwrap());
+
+    let mut group = c.benchmark_group("distance transform");
+    for algorithm in [
+        DistanceAlgorithm::FelzenszwalbHuttenlocher,
+        DistanceAlgorithm::Ssedt8,
+        DistanceAlgorithm::DeadReckoning,
+        DistanceAlgorithm::JumpFlooding,
+    ] {
+        group.bench_function(format!("{algorithm:?}"), |b| {
+            let mut renderer = SdfRenderer::with_algorithm(algorithm);
+            let mut output = vec![0f64; (16 + 6) * (19 + 6)];
+            b.iter(|| renderer.render_sdf_into(&bitmap, 8, &mut output))
+        });
+    }
+    group.finish();
+}
+
+/// Benchmarks a large, non-glyph mask (a filled circle), where the distance transform dominates.
+/// Enable the `rayon` feature to compare the parallel version.
+pub fn benchmark_large_sdf(c: &mut Criterion) {
+    let size = 1024;
+    let alpha: Vec<u8> = (0..size * size)
+        .map(|i| {
+            let (x, y) = ((i % size) as f64 - 512.0, (i / size) as f64 - 512.0);
+            let coverage = (400.0 - (x * x + y * y).sqrt()).clamp(0.0, 1.0);
+            (coverage * 255.0) as u8
+        })
+        .collect();
+    let bitmap = black_box(BitmapGlyph::from_unbuffered(&alpha, size, size, 8).unwrap());
+
+    let mut group = c.benchmark_group("large bitmap");
+    group.sample_size(10);
+    group.bench_function("benchmark 1024x1024 sdf gen", |b| {
+        let mut renderer = SdfRenderer::new();
+        let mut output = vec![0f64; (size + 16) * (size + 16)];
+        b.iter(|| renderer.render_sdf_into(&bitmap, 8, &mut output))
+    });
+    group.finish();
 }
 
-criterion_group!(benches, benchmark_sdf);
+criterion_group!(
+    benches,
+    benchmark_sdf,
+    benchmark_algorithms,
+    benchmark_large_sdf
+);
 criterion_main!(benches);
@@ -1,4 +1,7 @@
-use crate::SdfGlyphError;
+use crate::edt::Edt;
+use crate::{
+    DistanceAlgorithm, DistanceTransform, LinearU8Encoder, SdfEncoder, SdfFloat, SdfGlyphError,
+};
 
 /// A raw bitmap containing only the alpha channel.
 #[derive(Debug, PartialEq, Eq)]
@@ -16,6 +19,102 @@ pub struct BitmapGlyph {
     /// The number of pixels buffering the glyph on all sides. You can add a buffer to a
     /// raw bitmap using the [`Self::from_unbuffered()`] constructor.
     pub(crate) buffer: usize,
+
+    /// How antialiased pixels are converted into distances to the outline.
+    pub(crate) edge_seeding: EdgeSeeding,
+}
+
+/// Controls how the distance to the outline is estimated for antialiased (partially covered)
+/// pixels before running the distance transform.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EdgeSeeding {
+    /// Treats the coverage of each pixel on its own as a distance: a pixel with alpha `a`
+    /// (from 0 to 1) is `0.5 - a` pixels outside the outline. This is what TinySDF has
+    /// traditionally done.
+    #[default]
+    Coverage,
+
+    /// Estimates the position of the outline within each antialiased pixel from its coverage
+    /// and the direction of the local alpha gradient (computed from its 8 neighbours), as
+    /// described by Gustavson & Strand in
+    /// [Anti-aliased Euclidean distance transform](https://doi.org/10.1016/j.patrec.2010.08.010).
+    ///
+    /// This is noticeably more accurate for diagonal and curved edges, which matters most for
+    /// small glyphs. Horizontal and vertical edges come out the same as with
+    /// [`EdgeSeeding::Coverage`].
+    Gradient,
+}
+
+/// The format of the pixels in a bitmap passed to [`BitmapGlyph::from_pixels`].
+///
+/// These mirror the pixel modes of FreeType bitmaps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelMode {
+    /// 1 bit per pixel, with the left-most pixel in the most significant bit of each byte.
+    Mono,
+
+    /// 8 bits of coverage per pixel.
+    Gray,
+
+    /// 2 bits of coverage per pixel, with the left-most pixel in the most significant bits.
+    Gray2,
+
+    /// 4 bits of coverage per pixel, with the left-most pixel in the most significant bits.
+    Gray4,
+
+    /// 3 bytes per pixel, one for each horizontal subpixel. The coverage of a pixel is the
+    /// average of its subpixels.
+    Lcd,
+
+    /// 3 rows per row of pixels, one for each vertical subpixel. The coverage of a pixel is
+    /// the average of its subpixels.
+    LcdV,
+
+    /// 4 bytes per pixel, in (premultiplied) BGRA order. The alpha channel is used as the
+    /// coverage, so colour glyphs such as emoji produce a silhouette.
+    Bgra,
+}
+
+impl PixelMode {
+    /// The number of bytes needed to store a row of `width` pixels, without padding.
+    fn row_bytes(self, width: usize) -> usize {
+        match self {
+            PixelMode::Mono => width.div_ceil(8),
+            PixelMode::Gray | PixelMode::LcdV => width,
+            PixelMode::Gray2 => width.div_ceil(4),
+            PixelMode::Gray4 => width.div_ceil(2),
+            PixelMode::Lcd => width * 3,
+            PixelMode::Bgra => width * 4,
+        }
+    }
+
+    /// The number of rows of data per row of pixels.
+    fn rows_per_pixel(self) -> usize {
+        if self == PixelMode::LcdV {
+            3
+        } else {
+            1
+        }
+    }
+
+    /// Returns the coverage of pixel `x`, where `rows` holds the pixel's row(s) of data.
+    fn coverage(self, rows: &[&[u8]], x: usize) -> u8 {
+        let row = rows[0];
+        match self {
+            PixelMode::Mono => ((row[x / 8] >> (7 - x % 8)) & 1) * 255,
+            PixelMode::Gray => row[x],
+            PixelMode::Gray2 => ((row[x / 4] >> (6 - 2 * (x % 4))) & 3) * 85,
+            PixelMode::Gray4 => ((row[x / 2] >> (4 - 4 * (x % 2))) & 15) * 17,
+            PixelMode::Lcd => average(&row[x * 3..x * 3 + 3]),
+            PixelMode::LcdV => average(&[rows[0][x], rows[1][x], rows[2][x]]),
+            PixelMode::Bgra => row[x * 4 + 3],
+        }
+    }
+}
+
+fn average(subpixels: &[u8]) -> u8 {
+    let sum: u32 = subpixels.iter().map(|&value| u32::from(value)).sum();
+    ((sum + 1) / 3) as u8
 }
 
 impl BitmapGlyph {
@@ -46,6 +145,7 @@ impl BitmapGlyph {
             width,
             height,
             buffer,
+            edge_seeding: EdgeSeeding::default(),
         })
     }
 
@@ -88,132 +188,387 @@ impl BitmapGlyph {
             width,
             height,
             buffer,
+            edge_seeding: EdgeSeeding::default(),
+        })
+    }
+
+    /// Creates a new bitmap from 8-bit alpha data with padded rows, buffered by a given amount.
+    ///
+    /// This is like [`Self::from_unbuffered()`], but each row of the input starts `stride`
+    /// bytes after the previous one. See [`Self::from_pixels()`] for details.
+    pub fn from_unbuffered_with_stride(
+        alpha: &[u8],
+        width: usize,
+        height: usize,
+        stride: isize,
+        buffer: usize,
+    ) -> Result<BitmapGlyph, SdfGlyphError> {
+        Self::from_pixels(alpha, width, height, stride, PixelMode::Gray, buffer)
+    }
+
+    /// Creates a new bitmap from data in any [`PixelMode`], buffered by a given amount.
+    ///
+    /// This accepts the bitmaps produced by font renderers such as FreeType as they are.
+    /// `width` and `height` are in pixels (so a [`PixelMode::Lcd`] bitmap has `3 * width`
+    /// bytes of data per row). Each row of data starts `stride` bytes after the previous one,
+    /// which must be at least the size of a row, but may be larger due to padding.
+    ///
+    /// A negative `stride` means the rows are stored bottom up, as with a negative FreeType
+    /// pitch. In either case, `data` must contain exactly `|stride|` bytes per row of data,
+    /// starting with the row at the lowest address.
+    pub fn from_pixels(
+        data: &[u8],
+        width: usize,
+        height: usize,
+        stride: isize,
+        pixel_mode: PixelMode,
+        buffer: usize,
+    ) -> Result<BitmapGlyph, SdfGlyphError> {
+        let pitch = stride.unsigned_abs();
+        let rows = height * pixel_mode.rows_per_pixel();
+        if pitch < pixel_mode.row_bytes(width) {
+            return Err(SdfGlyphError::InvalidStride(
+                stride,
+                pixel_mode.row_bytes(width),
+            ));
+        }
+        let expected = pitch * rows;
+        if data.len() != expected {
+            return Err(SdfGlyphError::InvalidDataDimensions(
+                "|stride| * rows",
+                expected,
+                data.len(),
+            ));
+        }
+
+        let row = |y: usize| {
+            let y = if stride < 0 { rows - 1 - y } else { y };
+            &data[y * pitch..(y + 1) * pitch]
+        };
+
+        let buffered_width = width + buffer * 2;
+        let mut buffered_data = vec![0u8; buffered_width * (height + buffer * 2)];
+        for y in 0..height {
+            let pixel_rows: Vec<&[u8]> = (0..pixel_mode.rows_per_pixel())
+                .map(|subpixel| row(y * pixel_mode.rows_per_pixel() + subpixel))
+                .collect();
+            let offset = (y + buffer) * buffered_width + buffer;
+            for x in 0..width {
+                buffered_data[offset + x] = pixel_mode.coverage(&pixel_rows, x);
+            }
+        }
+
+        Ok(BitmapGlyph {
+            alpha: buffered_data,
+            width,
+            height,
+            buffer,
+            edge_seeding: EdgeSeeding::default(),
         })
     }
 
+    /// The unbuffered width of the glyph in px.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The unbuffered height of the glyph in px.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The number of pixels buffering the glyph on all sides.
+    #[must_use]
+    pub fn buffer(&self) -> usize {
+        self.buffer
+    }
+
+    /// The buffered alpha bitmap, in row-major order.
+    #[must_use]
+    pub fn alpha(&self) -> &[u8] {
+        &self.alpha
+    }
+
+    /// Sets how antialiased pixels are converted into distances to the outline.
+    /// See [`EdgeSeeding`] for the options.
+    #[must_use]
+    pub fn with_edge_seeding(mut self, edge_seeding: EdgeSeeding) -> Self {
+        self.edge_seeding = edge_seeding;
+        self
+    }
+
+    /// Shrinks the glyph to the bounding box of its non-zero coverage, keeping the buffer around
+    /// it, so that no distances are computed (or stored) for empty rows and columns.
+    ///
+    /// Returns the number of columns and rows removed from the left and top, which should be
+    /// added to the left bearing and subtracted from the top bearing respectively. A blank glyph
+    /// (such as a space) is shrunk to nothing, buffer included, so it renders an empty field.
+    pub fn trim(&mut self) -> (usize, usize) {
+        let buffered_width = self.width + self.buffer * 2;
+        let coverage = |x: usize, y: usize| {
+            self.alpha[(y + self.buffer) * buffered_width + x + self.buffer] != 0
+        };
+        let rows: Vec<usize> = (0..self.height)
+            .filter(|&y| (0..self.width).any(|x| coverage(x, y)))
+            .collect();
+        let columns: Vec<usize> = (0..self.width)
+            .filter(|&x| rows.iter().any(|&y| coverage(x, y)))
+            .collect();
+        let (Some(&top), Some(&bottom), Some(&left), Some(&right)) =
+            (rows.first(), rows.last(), columns.first(), columns.last())
+        else {
+            self.alpha = Vec::new();
+            self.width = 0;
+            self.height = 0;
+            self.buffer = 0;
+            return (0, 0);
+        };
+
+        let width = right - left + 1;
+        let height = bottom - top + 1;
+        let new_buffered_width = width + self.buffer * 2;
+        let mut alpha = vec![0u8; new_buffered_width * (height + self.buffer * 2)];
+        for y in 0..height {
+            let source = (top + y + self.buffer) * buffered_width + left + self.buffer;
+            let destination = (y + self.buffer) * new_buffered_width + self.buffer;
+            alpha[destination..destination + width]
+                .copy_from_slice(&self.alpha[source..source + width]);
+        }
+
+        self.alpha = alpha;
+        self.width = width;
+        self.height = height;
+        (left, top)
+    }
+
     /// Render a signed distance field for the given bitmap, recording distances
     /// out to `radius` pixels from the shape outline (the rest will be clamped).
     /// The range of the output field is [-1.0, 1.0], normalised to units of `radius`.
+    ///
+    /// This allocates fresh working memory for every call. If you are rendering many glyphs,
+    /// an [`SdfRenderer`] will let you reuse it.
     #[must_use]
     pub fn render_sdf(&self, radius: usize) -> Vec<f64> {
-        // Create two bitmaps, one for the pixels outside the filled area, and another for
-        // values inside it.
-        let mut outer_df: Vec<f64> = self
-            .alpha
-            .iter()
-            .map(|alpha| {
-                if *alpha == 0 {
-                    f64::MAX // Perfectly outside the shape
-                } else {
-                    // Values with alpha < 50% will be treated as progressively
-                    // further "outside" the shape as their alpha decreases. Alpha > 50%
-                    // will be treated as "inside" the shape and get a zero value here.
-                    0f64.max(0.5 - (*alpha as f64 / 255.0)).powi(2)
-                }
-            })
-            .collect();
+        SdfRenderer::new().render_sdf(self, radius)
+    }
 
-        let mut inner_df: Vec<f64> = self
-            .alpha
-            .iter()
-            .map(|alpha| {
-                if *alpha == 255 {
-                    f64::MAX // Perfectly inside the shape
-                } else {
-                    // Values with alpha > 50% will be treated as progressively
-                    // further "inside" the shape as their alpha decreases. Alpha < 50%
-                    // will be treated as "outside" the shape and get a zero value here.
-                    0f64.max((*alpha as f64 / 255.0) - 0.5).powi(2)
-                }
-            })
-            .collect();
+    /// Renders a signed distance field like [`Self::render_sdf`], but computes it using `f32`
+    /// rather than `f64`, which halves the memory required.
+    ///
+    /// The results are not bit-for-bit identical to those of [`Self::render_sdf`],
+    /// but they are plenty precise for anything that ends up quantised to bytes.
+    #[must_use]
+    pub fn render_sdf_f32(&self, radius: usize) -> Vec<f32> {
+        SdfRenderer::new().render_sdf(self, radius)
+    }
+}
+
+/// A reusable context for rendering signed distance fields.
+///
+/// The renderer owns the scratch buffers needed by the distance transform and grows them as
+/// needed, so rendering many glyphs in a row (e.g. an entire font) does not allocate for every
+/// glyph, row and column.
+///
+/// The renderer is generic over the floating point type used for the computation;
+/// see [`SdfFloat`] for details. It uses the exact Felzenszwalb & Huttenlocher distance
+/// transform by default, but any [`DistanceTransform`] can be plugged in. The distances
+/// themselves are computed with an [`Edt`].
+#[derive(Debug)]
+pub struct SdfRenderer<F: SdfFloat = f64> {
+    edt: Edt<F>,
+}
 
-        let buffered_width = self.width + self.buffer + self.buffer;
-        let buffered_height = self.height + self.buffer + self.buffer;
-
-        // Per page 8 (422), the 2D distance transform can be obtained by computing the
-        // one-dimensional distance transform along each column first and then computing
-        // the transform along each row of the result. We run the transform over both the
-        // outer and inner to get the respective Euclidean squared distances
-        // (the math is much easier this way).
-        for col in 0..buffered_width {
-            dt(&mut outer_df, col, buffered_width, buffered_height);
-            dt(&mut inner_df, col, buffered_width, buffered_height);
+impl<F: SdfFloat> Default for SdfRenderer<F> {
+    fn default() -> Self {
+        Self::with_algorithm(DistanceAlgorithm::default())
+    }
+}
+
+impl<F: SdfFloat> SdfRenderer<F> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a renderer which uses one of the built-in distance transform algorithms.
+    #[must_use]
+    pub fn with_algorithm(algorithm: DistanceAlgorithm) -> Self {
+        Self {
+            edt: Edt::with_algorithm(algorithm),
         }
+    }
 
-        for row in 0..buffered_height {
-            dt(&mut outer_df, row * buffered_width, 1, buffered_width);
-            dt(&mut inner_df, row * buffered_width, 1, buffered_width);
+    /// Creates a renderer which uses a custom distance transform.
+    #[must_use]
+    pub fn with_transform<T: DistanceTransform<F> + 'static>(transform: T) -> Self {
+        Self {
+            edt: Edt::with_transform(transform),
         }
+    }
 
-        outer_df
-            .iter()
-            .zip(inner_df.iter())
-            .map(|(outer_df, inner_df)| {
-                // Determine the euclidean distance inside or outside the alpha mask, then
-                // clamp the range according to the radius so that the overall range of the
-                // output field is [-1, 1] as a percentage of the radius.
-                ((outer_df.sqrt() - inner_df.sqrt()) / radius as f64).clamp(-1.0, 1.0)
-            })
-            .collect()
+    /// Renders a signed distance field for the given bitmap into a new vector.
+    ///
+    /// See [`BitmapGlyph::render_sdf`] for details.
+    pub fn render_sdf(&mut self, bitmap: &BitmapGlyph, radius: usize) -> Vec<F> {
+        let mut output = vec![F::ZERO; bitmap.alpha.len()];
+        self.render_sdf_into(bitmap, radius, &mut output)
+            .expect("The output buffer is always sized to match");
+        output
     }
-}
 
-/// An O(n) Euclidean Distance Transform algorithm.
-/// See page 6 (420) of [paper](http://cs.brown.edu/people/pfelzens/papers/dt-final.pdf) for details and
-/// further discussion of the math behind this.
-fn dt(grid: &mut [f64], offset: usize, step_by: usize, size: usize) {
-    // For our purposes, f is a one-dimensional slice of the grid
-    let mut f = vec![0f64; size];
-    let mut src = offset;
-    for dst in f.iter_mut() {
-        *dst = grid[src];
-        src += step_by;
-    }
-
-    // It may be possible to make this more functional in style,
-    // but for now this is more or less a "dumb" transcription of
-    // the algorithm presented in the paper by Felzenszwalb & Huttenlocher.
-    let mut k = 0;
-    let mut v = vec![0; size];
-    let mut z = vec![0f64; size + 1];
-    let mut s: f64;
-
-    z[0] = f64::MIN;
-    z[1] = f64::MAX;
-
-    for q in 1..size {
-        loop {
-            let q2 = (q * q) as f64;
-            let vk2 = (v[k] * v[k]) as f64;
-            let denom = (2 * q - 2 * v[k]) as f64;
-            s = ((f[q] + q2) - (f[v[k]] + vk2)) / denom;
-
-            if s <= z[k] {
-                k -= 1;
-            } else {
-                k += 1;
-                v[k] = q;
-                z[k] = s;
-                z[k + 1] = f64::MAX;
+    /// Renders a signed distance field for the given bitmap into a caller-provided slice.
+    ///
+    /// The output must be exactly as large as the buffered bitmap:
+    /// `(width + buffer * 2) * (height + buffer * 2)`.
+    /// See [`BitmapGlyph::render_sdf`] for details on the output values.
+    pub fn render_sdf_into(
+        &mut self,
+        bitmap: &BitmapGlyph,
+        radius: usize,
+        output: &mut [F],
+    ) -> Result<(), SdfGlyphError> {
+        if output.len() != bitmap.alpha.len() {
+            return Err(SdfGlyphError::InvalidDataDimensions(
+                "(width + buffer * 2) * (height + buffer * 2)",
+                bitmap.alpha.len(),
+                output.len(),
+            ));
+        }
+
+        let buffered_width = bitmap.width + bitmap.buffer + bitmap.buffer;
+        let buffered_height = bitmap.height + bitmap.buffer + bitmap.buffer;
 
-                break;
+        match bitmap.edge_seeding {
+            EdgeSeeding::Coverage => self.seed_from_coverage(&bitmap.alpha),
+            EdgeSeeding::Gradient => {
+                self.seed_from_gradient(&bitmap.alpha, buffered_width, buffered_height);
             }
         }
+
+        // Determine the euclidean distance inside or outside the alpha mask, then
+        // clamp the range according to the radius so that the overall range of the
+        // output field is [-1, 1] as a percentage of the radius.
+        self.edt
+            .signed_distances_from_seeds(buffered_width, buffered_height, output);
+        for output in output.iter_mut() {
+            *output = (*output / F::from_usize(radius)).clamp(F::ZERO - F::ONE, F::ONE);
+        }
+
+        Ok(())
+    }
+
+    /// Seeds the outer and inner grids using only the coverage of each pixel.
+    fn seed_from_coverage(&mut self, alpha: &[u8]) {
+        // Create two bitmaps, one for the pixels outside the filled area, and another for
+        // values inside it.
+        self.edt.outer.clear();
+        self.edt.outer.extend(alpha.iter().map(|alpha| {
+            if *alpha == 0 {
+                F::MAX // Perfectly outside the shape
+            } else {
+                // Values with alpha < 50% will be treated as progressively
+                // further "outside" the shape as their alpha decreases. Alpha > 50%
+                // will be treated as "inside" the shape and get a zero value here.
+                let df = F::ZERO.max(F::HALF - (F::from_u8(*alpha) / F::from_u8(255)));
+                df * df
+            }
+        }));
+
+        self.edt.inner.clear();
+        self.edt.inner.extend(alpha.iter().map(|alpha| {
+            if *alpha == 255 {
+                F::MAX // Perfectly inside the shape
+            } else {
+                // Values with alpha > 50% will be treated as progressively
+                // further "inside" the shape as their alpha decreases. Alpha < 50%
+                // will be treated as "outside" the shape and get a zero value here.
+                let df = F::ZERO.max((F::from_u8(*alpha) / F::from_u8(255)) - F::HALF);
+                df * df
+            }
+        }));
     }
 
-    k = 0;
-    for q in 0..size {
-        let qf64 = q as f64;
-        while z[k + 1] < qf64 {
-            k += 1;
+    /// Seeds the outer and inner grids using an estimate of the outline position within each
+    /// antialiased pixel, based on its coverage and the local alpha gradient.
+    fn seed_from_gradient(&mut self, alpha: &[u8], width: usize, height: usize) {
+        self.edt.outer.clear();
+        self.edt.inner.clear();
+
+        for (i, a) in alpha.iter().enumerate() {
+            let (outer, inner) = match *a {
+                0 => (F::MAX, F::ZERO),
+                255 => (F::ZERO, F::MAX),
+                _ => {
+                    let (gx, gy) = gradient(alpha, width, height, i);
+                    let df = edge_distance(gx, gy, F::from_u8(*a) / F::from_u8(255));
+                    let outer = F::ZERO.max(df);
+                    let inner = F::ZERO.max(F::ZERO - df);
+                    (outer * outer, inner * inner)
+                }
+            };
+            self.edt.outer.push(outer);
+            self.edt.inner.push(inner);
         }
-        let vkf64 = v[k] as f64;
-        grid[offset + q * step_by] = (qf64 - vkf64) * (qf64 - vkf64) + f[v[k]];
     }
 }
 
-/// Compresses a `Vec<f64>` into a `Vec<u8>` for efficiency.
+/// Estimates the direction of the alpha gradient at pixel `i` using a Sobel-like operator
+/// (with weights of √2 rather than 2, which is isotropic for small gradients).
+///
+/// Pixels on the border of the bitmap get a zero gradient.
+fn gradient<F: SdfFloat>(alpha: &[u8], width: usize, height: usize, i: usize) -> (F, F) {
+    let (x, y) = (i % width, i / width);
+    if x == 0 || y == 0 || x + 1 == width || y + 1 == height {
+        return (F::ZERO, F::ZERO);
+    }
+
+    let a = |offset_x: usize, offset_y: usize| {
+        F::from_u8(alpha[(y + offset_y - 1) * width + x + offset_x - 1]) / F::from_u8(255)
+    };
+    let sqrt2 = F::from_usize(2).sqrt();
+
+    let gx = a(2, 0) + sqrt2 * a(2, 1) + a(2, 2) - a(0, 0) - sqrt2 * a(0, 1) - a(0, 2);
+    let gy = a(0, 2) + sqrt2 * a(1, 2) + a(2, 2) - a(0, 0) - sqrt2 * a(1, 0) - a(2, 0);
+
+    let length = (gx * gx + gy * gy).sqrt();
+    if length > F::ZERO {
+        (gx / length, gy / length)
+    } else {
+        (F::ZERO, F::ZERO)
+    }
+}
+
+/// Estimates the signed distance from the centre of a pixel with coverage `a` to the outline,
+/// assuming the outline is a straight line perpendicular to the (normalised) gradient.
+/// Positive distances are outside the shape.
+fn edge_distance<F: SdfFloat>(gx: F, gy: F, a: F) -> F {
+    if gx == F::ZERO || gy == F::ZERO {
+        // Horizontal or vertical edges (or no gradient at all) are linear in the coverage
+        return F::HALF - a;
+    }
+
+    // By symmetry, only the first octant needs to be considered
+    let (gx, gy) = (gx.abs(), gy.abs());
+    let (gx, gy) = if gx < gy { (gy, gx) } else { (gx, gy) };
+
+    // The coverage at which the edge crosses a corner of the pixel
+    let a1 = F::HALF * gy / gx;
+    let two = F::from_usize(2);
+    if a < a1 {
+        // The edge only clips a corner of the pixel
+        F::HALF * (gx + gy) - (two * gx * gy * a).sqrt()
+    } else if a < F::ONE - a1 {
+        // The edge crosses two opposite sides of the pixel
+        (F::HALF - a) * gx
+    } else {
+        // The edge clips the opposite corner
+        (two * gx * gy * (F::ONE - a)).sqrt() - F::HALF * (gx + gy)
+    }
+}
+
+/// Compresses a signed distance field (of `f64` or `f32` values) into a `Vec<u8>` for efficiency.
 ///
 /// The highest `cutoff` percentage of values in the range (0-255) will be used to encode
 /// negative values (points inside the glyph). This can be tuned based on the intended
@@ -221,25 +576,55 @@ fn dt(grid: &mut [f64], offset: usize, step_by: usize, size: usize) {
 ///
 /// The `cutoff` value must be in the range (0, 1) - non-inclusive on both sides.
 /// Values outside this range make no sense and will result in an error.
-pub fn clamp_to_u8(sdf: &[f64], cutoff: f64) -> Result<Vec<u8>, SdfGlyphError> {
-    if cutoff <= 0.0 || cutoff >= 1.0 {
-        return Err(SdfGlyphError::InvalidCutoff(cutoff));
-    }
-    Ok(sdf
-        .iter()
+pub fn clamp_to_u8<F: SdfFloat>(sdf: &[F], cutoff: f64) -> Result<Vec<u8>, SdfGlyphError> {
+    Ok(LinearU8Encoder::new(cutoff)?.encode_sdf(sdf))
+}
+
+/// Decodes bytes produced by [`clamp_to_u8`] (e.g. the bitmap of a stored PBF glyph) back into
+/// a signed distance field, normalised to the radius the field was rendered with.
+///
+/// The `cutoff` must match the one used for encoding. Quantisation cannot be undone exactly,
+/// so values are only accurate to within 1/255, and anything which was clamped during encoding
+/// stays clamped.
+pub fn u8_to_sdf(bytes: &[u8], cutoff: f64) -> Result<Vec<f64>, SdfGlyphError> {
+    Ok(LinearU8Encoder::new(cutoff)?.decode_sdf(bytes))
+}
+
+/// Reconstructs an alpha (coverage) bitmap from a signed distance field, like a renderer would.
+///
+/// `radius` is the radius the field was rendered with. Pixels closer than `threshold` pixels
+/// to the outline (or inside it) are considered filled, so a positive threshold makes the
+/// shape bolder and a negative one thinner. `softness` is the width (in pixels) of the
+/// antialiased transition at the edge; 1.0 approximates the antialiasing of the bitmap the
+/// field was generated from, and 0.0 gives hard edges.
+#[must_use]
+pub fn reconstruct_alpha<F: SdfFloat>(
+    sdf: &[F],
+    radius: usize,
+    threshold: f64,
+    softness: f64,
+) -> Vec<u8> {
+    sdf.iter()
         .map(|v| {
-            // Map the values back into the single byte integer range.
-            // Note: casting from a float to an integer performs a saturating
-            // cast in Rust, removing the need for special logic.
-            // See https://doc.rust-lang.org/nomicon/casts.html.
-            (255.0 - 255.0 * (v + cutoff)) as u8
+            let distance = v.to_f64() * radius as f64 - threshold;
+            let coverage = if softness > 0.0 {
+                (0.5 - distance / softness).clamp(0.0, 1.0)
+            } else if distance <= 0.0 {
+                1.0
+            } else {
+                0.0
+            };
+            (coverage * 255.0).round() as u8
         })
-        .collect())
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{clamp_to_u8, BitmapGlyph};
+    use super::{
+        clamp_to_u8, reconstruct_alpha, u8_to_sdf, BitmapGlyph, EdgeSeeding, PixelMode, SdfRenderer,
+    };
+    use crate::{DistanceAlgorithm, SdfGlyphError};
 
     #[test]
     fn test_empty_glyph_unbuffered() {
@@ -297,4 +682,331 @@ mod tests {
             sdf_data_f64.into_iter().filter(|x| *x < 0.0).count()
         );
     }
+
+    #[test]
+    #[allow(clippy::unreadable_literal)]
+    fn test_reused_renderer() {
+        let alpha = Vec::from(include!("../fixtures/glyph_alpha.json"));
+        let sdf_data_f64 = Vec::from(include!("../fixtures/glyph_sdf_f64.json"));
+        let large_bitmap = BitmapGlyph::new(alpha, 16, 19, 3).unwrap();
+        let small_bitmap = BitmapGlyph::from_unbuffered(&[0, 128, 255, 128], 2, 2, 3).unwrap();
+
+        // Rendering glyphs of different sizes with the same renderer must not leak state
+        // between them.
+        let mut renderer = SdfRenderer::new();
+        let mut output = vec![0f64; sdf_data_f64.len()];
+        for _ in 0..2 {
+            renderer
+                .render_sdf_into(&large_bitmap, 8, &mut output)
+                .unwrap();
+            assert_eq!(output, sdf_data_f64);
+            assert_eq!(
+                renderer.render_sdf(&small_bitmap, 8),
+                small_bitmap.render_sdf(8)
+            );
+        }
+
+        assert!(renderer
+            .render_sdf_into(&large_bitmap, 8, &mut output[1..])
+            .is_err());
+    }
+
+    #[test]
+    #[allow(clippy::unreadable_literal)]
+    fn test_f32_glyph() {
+        // The f32 pipeline won't be bit-for-bit identical, but once quantised, it should never
+        // be off by more than one.
+        let alpha = Vec::from(include!("../fixtures/glyph_alpha.json"));
+        let sdf_data_u8: Vec<u8> = Vec::from(include!("../fixtures/glyph_sdf_u8.json"));
+        let bitmap = BitmapGlyph::new(alpha, 16, 19, 3).unwrap();
+        let sdf = bitmap.render_sdf_f32(8);
+
+        let sdf_u8 = clamp_to_u8(&sdf, 0.25).unwrap();
+        assert_eq!(sdf_u8.len(), sdf_data_u8.len());
+        for (actual, expected) in sdf_u8.into_iter().zip(sdf_data_u8) {
+            assert!(
+                actual.abs_diff(expected) <= 1,
+                "Expected {expected}, got {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_approximate_algorithms() {
+        // The approximate distance transforms should produce nearly the same glyph. Dead
+        // reckoning is off by up to half a pixel in places, which is ~12 steps once quantised.
+        let alpha = Vec::from(include!("../fixtures/glyph_alpha.json"));
+        let sdf_data_u8: Vec<u8> = Vec::from(include!("../fixtures/glyph_sdf_u8.json"));
+        let bitmap = BitmapGlyph::new(alpha, 16, 19, 3).unwrap();
+
+        for (algorithm, tolerance) in [
+            (DistanceAlgorithm::Ssedt8, 0),
+            (DistanceAlgorithm::DeadReckoning, 12),
+            (DistanceAlgorithm::JumpFlooding, 0),
+        ] {
+            let mut renderer = SdfRenderer::<f64>::with_algorithm(algorithm);
+            let sdf_u8 = clamp_to_u8(&renderer.render_sdf(&bitmap, 8), 0.25).unwrap();
+            assert_eq!(sdf_u8.len(), sdf_data_u8.len());
+            for (actual, expected) in sdf_u8.into_iter().zip(sdf_data_u8.iter()) {
+                assert!(
+                    actual.abs_diff(*expected) <= tolerance,
+                    "{algorithm:?}: expected {expected}, got {actual}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_gradient_edge_seeding() {
+        // An antialiased (supersampled) circle, for which we know the exact distances
+        let (size, radius, centre_x, centre_y) = (40, 11.3, 20.4, 19.7);
+        let samples = 16;
+        let alpha: Vec<u8> = (0..size * size)
+            .map(|i| {
+                let (x, y) = ((i % size) as f64, (i / size) as f64);
+                let covered = (0..samples * samples)
+                    .filter(|sample| {
+                        let sample_x = x + (f64::from(sample % samples) + 0.5) / f64::from(samples);
+                        let sample_y = y + (f64::from(sample / samples) + 0.5) / f64::from(samples);
+                        (sample_x - centre_x).hypot(sample_y - centre_y) < radius
+                    })
+                    .count();
+                (255.0 * covered as f64 / f64::from(samples * samples)).round() as u8
+            })
+            .collect();
+
+        // Compares the SDF to the true distance to the circle at the antialiased pixels
+        let mean_error = |edge_seeding| {
+            let bitmap = BitmapGlyph::new(alpha.clone(), size, size, 0)
+                .unwrap()
+                .with_edge_seeding(edge_seeding);
+            let sdf = bitmap.render_sdf(8);
+            let errors: Vec<f64> = sdf
+                .iter()
+                .enumerate()
+                .filter_map(|(i, value)| {
+                    let (x, y) = ((i % size) as f64 + 0.5, (i / size) as f64 + 0.5);
+                    let expected = (x - centre_x).hypot(y - centre_y) - radius;
+                    (alpha[i] > 0 && alpha[i] < 255).then(|| (value * 8.0 - expected).abs())
+                })
+                .collect();
+            errors.iter().sum::<f64>() / errors.len() as f64
+        };
+
+        let coverage_error = mean_error(EdgeSeeding::Coverage);
+        let gradient_error = mean_error(EdgeSeeding::Gradient);
+        assert!(
+            gradient_error < coverage_error / 2.0,
+            "Gradient error {gradient_error}, coverage error {coverage_error}"
+        );
+    }
+
+    #[test]
+    fn test_gradient_edge_seeding_straight_edge() {
+        // Vertical edges are estimated the same way in both modes
+        let alpha = [0, 0, 64, 255, 255, 255].repeat(6);
+        let coverage = BitmapGlyph::new(alpha.clone(), 6, 6, 0).unwrap();
+        let gradient = BitmapGlyph::new(alpha, 6, 6, 0)
+            .unwrap()
+            .with_edge_seeding(EdgeSeeding::Gradient);
+
+        assert_eq!(coverage.render_sdf(4), gradient.render_sdf(4));
+    }
+
+    #[test]
+    fn test_u8_round_trip() {
+        let sdf_data_f64: Vec<f64> = Vec::from(include!("../fixtures/glyph_sdf_f64.json"));
+        let sdf_data_u8 = Vec::from(include!("../fixtures/glyph_sdf_u8.json"));
+
+        let decoded = u8_to_sdf(&sdf_data_u8, 0.25).unwrap();
+        for (decoded, original) in decoded.iter().zip(sdf_data_f64.iter()) {
+            // Values which were clamped when encoding stay clamped
+            let original = original.clamp(-0.25, 0.75);
+            assert!((decoded - original).abs() <= 1.0 / 255.0);
+        }
+        assert_eq!(clamp_to_u8(&decoded, 0.25).unwrap(), sdf_data_u8);
+
+        assert!(u8_to_sdf(&sdf_data_u8, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_reconstruct_alpha() {
+        let alpha: Vec<u8> = Vec::from(include!("../fixtures/glyph_alpha.json"));
+        let sdf_data_f64: Vec<f64> = Vec::from(include!("../fixtures/glyph_sdf_f64.json"));
+
+        // With a softness of one pixel, the reconstructed coverage should match the original
+        let reconstructed = reconstruct_alpha(&sdf_data_f64, 8, 0.0, 1.0);
+        let mean_error = reconstructed
+            .iter()
+            .zip(alpha.iter())
+            .map(|(actual, expected)| f64::from(actual.abs_diff(*expected)))
+            .sum::<f64>()
+            / alpha.len() as f64;
+        assert!(mean_error < 1.0, "Mean error {mean_error}");
+
+        // Hard edges only ever produce fully covered or empty pixels
+        let hard = reconstruct_alpha(&sdf_data_f64, 8, 0.0, 0.0);
+        assert!(hard.iter().all(|a| *a == 0 || *a == 255));
+
+        // A positive threshold emboldens the shape
+        let bold = reconstruct_alpha(&sdf_data_f64, 8, 1.0, 1.0);
+        let sum = |alpha: &[u8]| alpha.iter().map(|a| u32::from(*a)).sum::<u32>();
+        assert!(sum(&bold) > sum(&reconstructed));
+    }
+
+    #[test]
+    fn test_from_pixels() {
+        // A 3x2 glyph, with the coverage of each pixel (top to bottom) in every format
+        let expected = [255, 0, 255, 0, 255, 0];
+        let unbuffered = |data: &[u8], stride: isize, pixel_mode: PixelMode| -> Vec<u8> {
+            let bitmap = BitmapGlyph::from_pixels(data, 3, 2, stride, pixel_mode, 0).unwrap();
+            bitmap.alpha().to_vec()
+        };
+
+        // Rows padded to two bytes
+        assert_eq!(
+            unbuffered(&[0b1010_0000, 0xFF, 0b0100_0000, 0xFF], 2, PixelMode::Mono),
+            expected
+        );
+        assert_eq!(
+            unbuffered(&[0b1100_1100, 0b0011_0000], 1, PixelMode::Gray2),
+            expected
+        );
+        assert_eq!(
+            unbuffered(&[0xF0, 0xF0, 0x0F, 0x00], 2, PixelMode::Gray4),
+            expected
+        );
+        assert_eq!(
+            unbuffered(&[255, 0, 255, 7, 0, 255, 0, 7], 4, PixelMode::Gray),
+            expected
+        );
+        assert_eq!(
+            unbuffered(
+                &[
+                    255, 255, 255, 0, 0, 0, 255, 255, 255, //
+                    0, 0, 0, 255, 255, 255, 0, 0, 0,
+                ],
+                9,
+                PixelMode::Lcd
+            ),
+            expected
+        );
+        assert_eq!(
+            unbuffered(
+                &[
+                    255, 0, 255, 255, 0, 255, 255, 0, 255, //
+                    0, 255, 0, 0, 255, 0, 0, 255, 0,
+                ],
+                3,
+                PixelMode::LcdV
+            ),
+            expected
+        );
+        // Colour is ignored in favour of alpha
+        assert_eq!(
+            unbuffered(
+                &[
+                    9, 9, 9, 255, 9, 9, 9, 0, 9, 9, 9, 255, //
+                    9, 9, 9, 0, 9, 9, 9, 255, 9, 9, 9, 0,
+                ],
+                12,
+                PixelMode::Bgra
+            ),
+            expected
+        );
+
+        // Bottom-up rows
+        assert_eq!(
+            unbuffered(&[0, 255, 0, 255, 0, 255], -3, PixelMode::Gray),
+            expected
+        );
+        assert_eq!(
+            BitmapGlyph::from_unbuffered_with_stride(&[0, 255, 0, 255, 0, 255], 3, 2, -3, 0)
+                .unwrap()
+                .alpha(),
+            expected
+        );
+
+        // Partial coverage is scaled to the full range
+        assert_eq!(
+            unbuffered(&[0b0110_1100, 0], 1, PixelMode::Gray2),
+            [85, 170, 255, 0, 0, 0]
+        );
+        assert_eq!(
+            unbuffered(
+                &[30, 30, 30, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+                9,
+                PixelMode::Lcd
+            )[0],
+            30
+        );
+
+        // Buffering works as with the other constructors
+        let buffered =
+            BitmapGlyph::from_pixels(&[0b1010_0000, 0b0100_0000], 3, 2, 1, PixelMode::Mono, 1)
+                .unwrap();
+        assert_eq!(
+            buffered.alpha(),
+            [
+                0, 0, 0, 0, 0, //
+                0, 255, 0, 255, 0, //
+                0, 0, 255, 0, 0, //
+                0, 0, 0, 0, 0,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_pixels_invalid() {
+        assert!(matches!(
+            BitmapGlyph::from_pixels(&[0; 4], 3, 2, 2, PixelMode::Gray, 0),
+            Err(SdfGlyphError::InvalidStride(2, 3))
+        ));
+        assert!(matches!(
+            BitmapGlyph::from_pixels(&[0; 4], 3, 2, -2, PixelMode::Lcd, 0),
+            Err(SdfGlyphError::InvalidStride(-2, 9))
+        ));
+        assert!(matches!(
+            BitmapGlyph::from_pixels(&[0; 7], 3, 2, 4, PixelMode::Gray, 0),
+            Err(SdfGlyphError::InvalidDataDimensions(_, 8, 7))
+        ));
+        assert!(BitmapGlyph::from_pixels(&[0; 2], 16, 1, 2, PixelMode::Mono, 0).is_ok());
+    }
+
+    #[test]
+    fn test_trim() {
+        // A 4x4 glyph whose coverage only spans the middle two columns of the last two rows
+        let alpha = [
+            0, 0, 0, 0, //
+            0, 0, 0, 0, //
+            0, 10, 20, 0, //
+            0, 0, 30, 0,
+        ];
+        let mut bitmap = BitmapGlyph::from_unbuffered(&alpha, 4, 4, 1).unwrap();
+        assert_eq!(bitmap.trim(), (1, 2));
+        assert_eq!(
+            (bitmap.width(), bitmap.height(), bitmap.buffer()),
+            (2, 2, 1)
+        );
+        assert_eq!(
+            bitmap.alpha(),
+            [
+                0, 0, 0, 0, //
+                0, 10, 20, 0, //
+                0, 0, 30, 0, //
+                0, 0, 0, 0,
+            ]
+        );
+
+        // Trimming a tight bitmap changes nothing
+        let trimmed = bitmap.alpha().to_vec();
+        assert_eq!(bitmap.trim(), (0, 0));
+        assert_eq!(bitmap.alpha(), trimmed);
+
+        // Blank glyphs are dropped entirely
+        let mut blank = BitmapGlyph::from_unbuffered(&[0; 6], 3, 2, 3).unwrap();
+        assert_eq!(blank.trim(), (0, 0));
+        assert_eq!((blank.width(), blank.height(), blank.buffer()), (0, 0, 0));
+        assert!(blank.render_sdf(8).is_empty());
+    }
 }
@@ -0,0 +1,366 @@
+//! Euclidean distance transforms over arbitrary rasters.
+//!
+//! [`Edt`] exposes the distance transforms behind [`BitmapGlyph::render_sdf`] for any
+//! row-major 2D grid, such as masks for raster analysis or sprite generation. Distances are
+//! measured in pixels between pixel centres. Signed distances follow the same convention as
+//! glyphs: positive outside the shape and negative inside it.
+//!
+//! ```
+//! use sdf_glyph_renderer::edt::Edt;
+//!
+//! // A 3x3 mask with only the centre pixel set
+//! let mask = [false, false, false, false, true, false, false, false, false];
+//! let mut edt = Edt::<f64>::new();
+//!
+//! let distances = edt.unsigned_distances(&mask, 3, 3).unwrap();
+//! assert_eq!(distances[1], 1.0);
+//! assert_eq!(distances[0], 2f64.sqrt());
+//!
+//! let signed = edt.signed_distances(&mask, 3, 3).unwrap();
+//! assert_eq!(signed[4], -1.0);
+//! ```
+//!
+//! [`BitmapGlyph::render_sdf`]: crate::BitmapGlyph::render_sdf
+
+use crate::transform::{dt_with_nearest, DtScratch};
+use crate::{DistanceAlgorithm, DistanceTransform, SdfFloat, SdfGlyphError};
+
+/// A reusable context for computing Euclidean distance transforms.
+///
+/// Like [`SdfRenderer`](crate::SdfRenderer), this owns its scratch buffers, so transforming many
+/// grids in a row does not allocate for each one. It uses the exact Felzenszwalb & Huttenlocher
+/// distance transform by default, but any [`DistanceTransform`] can be plugged in.
+#[derive(Debug)]
+pub struct Edt<F: SdfFloat = f64> {
+    transform: Box<dyn DistanceTransform<F>>,
+    pub(crate) outer: Vec<F>,
+    pub(crate) inner: Vec<F>,
+    values: Vec<F>,
+    rows: Vec<usize>,
+    scratch: DtScratch<F>,
+}
+
+impl<F: SdfFloat> Default for Edt<F> {
+    fn default() -> Self {
+        Self::with_algorithm(DistanceAlgorithm::default())
+    }
+}
+
+impl<F: SdfFloat> Edt<F> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a context which uses one of the built-in distance transform algorithms.
+    #[must_use]
+    pub fn with_algorithm(algorithm: DistanceAlgorithm) -> Self {
+        Self::with_boxed_transform(algorithm.transform())
+    }
+
+    /// Creates a context which uses a custom distance transform.
+    #[must_use]
+    pub fn with_transform<T: DistanceTransform<F> + 'static>(transform: T) -> Self {
+        Self::with_boxed_transform(Box::new(transform))
+    }
+
+    fn with_boxed_transform(transform: Box<dyn DistanceTransform<F>>) -> Self {
+        Self {
+            transform,
+            outer: Vec::new(),
+            inner: Vec::new(),
+            values: Vec::new(),
+            rows: Vec::new(),
+            scratch: DtScratch::default(),
+        }
+    }
+
+    /// Replaces every value of a `width` x `height` grid with the squared distance to the
+    /// nearest feature.
+    ///
+    /// Features are pixels with a value other than `F::MAX`, which is added to the distance to
+    /// them (so zero for a plain feature). Pixels with no features at all are left at `F::MAX`.
+    /// See [`DistanceTransform`] for details.
+    pub fn squared_distances(
+        &mut self,
+        grid: &mut [F],
+        width: usize,
+        height: usize,
+    ) -> Result<(), SdfGlyphError> {
+        check_dimensions(grid.len(), width, height)?;
+        self.transform.transform(grid, width, height);
+        Ok(())
+    }
+
+    /// Computes the squared distances like [`Self::squared_distances`], and returns the index
+    /// of the nearest feature of each pixel (a Voronoi map of the features).
+    ///
+    /// Ties between equally near features are broken arbitrarily, and pixels are mapped to
+    /// `None` if the grid has no features. This always uses the exact Felzenszwalb &
+    /// Huttenlocher transform, regardless of the algorithm this context was created with.
+    pub fn squared_distances_with_nearest(
+        &mut self,
+        grid: &mut [F],
+        width: usize,
+        height: usize,
+    ) -> Result<Vec<Option<usize>>, SdfGlyphError> {
+        check_dimensions(grid.len(), width, height)?;
+        let Self {
+            values,
+            rows,
+            scratch,
+            ..
+        } = self;
+
+        // The column pass records the row of the nearest feature in each column...
+        rows.clear();
+        rows.resize(grid.len(), 0);
+        values.clear();
+        values.resize(height, F::ZERO);
+        for x in 0..width {
+            for (y, value) in values.iter_mut().enumerate() {
+                *value = grid[y * width + x];
+            }
+            dt_with_nearest(values, scratch, |y, nearest_y| {
+                rows[y * width + x] = nearest_y;
+            });
+            for (y, value) in values.iter().enumerate() {
+                grid[y * width + x] = *value;
+            }
+        }
+
+        // ...and the row pass picks the nearest of those columns
+        let mut nearest = vec![None; grid.len()];
+        if width == 0 {
+            return Ok(nearest);
+        }
+        for (y, (row, nearest)) in grid
+            .chunks_exact_mut(width)
+            .zip(nearest.chunks_exact_mut(width))
+            .enumerate()
+        {
+            dt_with_nearest(row, scratch, |x, nearest_x| {
+                nearest[x] = Some(rows[y * width + nearest_x] * width + nearest_x);
+            });
+            for (value, nearest) in row.iter().zip(nearest.iter_mut()) {
+                if *value == F::MAX {
+                    *nearest = None;
+                }
+            }
+        }
+
+        Ok(nearest)
+    }
+
+    /// Returns the distance from every pixel of a `width` x `height` mask to the nearest set
+    /// pixel (zero for the set pixels themselves), or `F::MAX` if no pixels are set.
+    pub fn unsigned_distances(
+        &mut self,
+        mask: &[bool],
+        width: usize,
+        height: usize,
+    ) -> Result<Vec<F>, SdfGlyphError> {
+        check_dimensions(mask.len(), width, height)?;
+        let mut distances: Vec<F> = mask
+            .iter()
+            .map(|set| if *set { F::ZERO } else { F::MAX })
+            .collect();
+        self.transform.transform(&mut distances, width, height);
+        for distance in &mut distances {
+            *distance = root(*distance);
+        }
+
+        Ok(distances)
+    }
+
+    /// Returns the signed distance field of a `width` x `height` mask, where the set pixels
+    /// are inside the shape.
+    ///
+    /// Pixels outside the shape get the (positive) distance to the nearest set pixel, and
+    /// pixels inside it get the negated distance to the nearest unset pixel. If the mask is
+    /// entirely unset (or set), the distances are `F::MAX` (or `-F::MAX`).
+    pub fn signed_distances(
+        &mut self,
+        mask: &[bool],
+        width: usize,
+        height: usize,
+    ) -> Result<Vec<F>, SdfGlyphError> {
+        check_dimensions(mask.len(), width, height)?;
+        self.outer.clear();
+        self.outer
+            .extend(mask.iter().map(|set| if *set { F::ZERO } else { F::MAX }));
+        self.inner.clear();
+        self.inner
+            .extend(mask.iter().map(|set| if *set { F::MAX } else { F::ZERO }));
+
+        let mut distances = vec![F::ZERO; mask.len()];
+        self.signed_distances_from_seeds(width, height, &mut distances);
+        Ok(distances)
+    }
+
+    /// Transforms the seeded outer and inner grids (the squared distances to the outside and
+    /// inside of a shape), writing the signed distances into `output`.
+    pub(crate) fn signed_distances_from_seeds(
+        &mut self,
+        width: usize,
+        height: usize,
+        output: &mut [F],
+    ) {
+        // We run the transform over both the outer and inner to get the respective
+        // Euclidean squared distances (the math is much easier this way).
+        self.transform.transform(&mut self.outer, width, height);
+        self.transform.transform(&mut self.inner, width, height);
+
+        for ((output, outer), inner) in output
+            .iter_mut()
+            .zip(self.outer.iter())
+            .zip(self.inner.iter())
+        {
+            *output = root(*outer) - root(*inner);
+        }
+    }
+}
+
+/// Takes the square root of a squared distance, keeping `F::MAX` (no features) as it is.
+fn root<F: SdfFloat>(squared: F) -> F {
+    if squared == F::MAX {
+        F::MAX
+    } else {
+        squared.sqrt()
+    }
+}
+
+fn check_dimensions(len: usize, width: usize, height: usize) -> Result<(), SdfGlyphError> {
+    if len != width * height {
+        return Err(SdfGlyphError::InvalidDataDimensions(
+            "width * height",
+            width * height,
+            len,
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Edt;
+    use crate::{BitmapGlyph, DistanceAlgorithm, SdfGlyphError};
+
+    /// A sparse, irregular mask.
+    fn irregular_mask(width: usize, height: usize) -> Vec<bool> {
+        (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                (x * x + y * 3) % 97 < 4
+            })
+            .collect()
+    }
+
+    fn squared_distance(a: usize, b: usize, width: usize) -> usize {
+        let dx = (a % width).abs_diff(b % width);
+        let dy = (a / width).abs_diff(b / width);
+        dx * dx + dy * dy
+    }
+
+    #[test]
+    fn test_nearest_features() {
+        let (width, height) = (37, 23);
+        let mask = irregular_mask(width, height);
+        let features: Vec<usize> = (0..mask.len()).filter(|i| mask[*i]).collect();
+        let mut grid: Vec<f64> = mask
+            .iter()
+            .map(|set| if *set { 0.0 } else { f64::MAX })
+            .collect();
+
+        let mut edt = Edt::new();
+        let nearest = edt
+            .squared_distances_with_nearest(&mut grid, width, height)
+            .unwrap();
+        for (p, (distance, nearest)) in grid.iter().zip(&nearest).enumerate() {
+            let nearest = nearest.expect("Every pixel has a nearest feature");
+            assert!(mask[nearest]);
+
+            // The nearest feature is at the transformed distance, which is the minimum
+            let expected = features
+                .iter()
+                .map(|s| squared_distance(p, *s, width))
+                .min()
+                .unwrap();
+            assert_eq!(*distance, expected as f64);
+            assert_eq!(squared_distance(p, nearest, width), expected);
+        }
+
+        // The distances match the plain transform
+        let mut plain: Vec<f64> = mask
+            .iter()
+            .map(|set| if *set { 0.0 } else { f64::MAX })
+            .collect();
+        edt.squared_distances(&mut plain, width, height).unwrap();
+        assert_eq!(plain, grid);
+    }
+
+    #[test]
+    fn test_no_features() {
+        let mut edt = Edt::<f32>::new();
+        let mut grid = vec![f32::MAX; 6];
+        let nearest = edt.squared_distances_with_nearest(&mut grid, 3, 2).unwrap();
+        assert_eq!(nearest, vec![None; 6]);
+        assert!(grid.iter().all(|value| *value == f32::MAX));
+
+        assert_eq!(
+            edt.unsigned_distances(&[false; 6], 3, 2).unwrap(),
+            vec![f32::MAX; 6]
+        );
+        assert_eq!(
+            edt.signed_distances(&[true; 6], 3, 2).unwrap(),
+            vec![-f32::MAX; 6]
+        );
+        assert!(edt
+            .squared_distances_with_nearest(&mut [], 0, 0)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            edt.unsigned_distances(&[false; 5], 3, 2),
+            Err(SdfGlyphError::InvalidDataDimensions(_, 6, 5))
+        ));
+    }
+
+    #[test]
+    fn test_signed_distances() {
+        // A 2x2 square in the middle of a 6x4 mask
+        #[rustfmt::skip]
+        let mask = [
+            false, false, false, false, false, false,
+            false, false, true, true, false, false,
+            false, false, true, true, false, false,
+            false, false, false, false, false, false,
+        ];
+        let mut edt = Edt::<f64>::with_algorithm(DistanceAlgorithm::Ssedt8);
+        let signed = edt.signed_distances(&mask, 6, 4).unwrap();
+        let unsigned = edt.unsigned_distances(&mask, 6, 4).unwrap();
+        assert_eq!(signed[8], -1.0);
+        assert_eq!(signed[7], 1.0);
+        assert_eq!(signed[6], 2.0);
+        assert_eq!(signed[0], 5f64.sqrt());
+        for ((signed, unsigned), set) in signed.iter().zip(&unsigned).zip(mask) {
+            if set {
+                assert_eq!(*unsigned, 0.0);
+            } else {
+                assert_eq!(signed, unsigned);
+            }
+        }
+
+        // Glyphs with fully covered and empty pixels produce the same field, normalised to the
+        // radius
+        let alpha: Vec<u8> = mask.iter().map(|set| if *set { 255 } else { 0 }).collect();
+        let sdf = BitmapGlyph::new(alpha, 6, 4, 0).unwrap().render_sdf(8);
+        let expected: Vec<f64> = edt
+            .signed_distances(&mask, 6, 4)
+            .unwrap()
+            .iter()
+            .map(|distance| distance / 8.0)
+            .collect();
+        assert_eq!(sdf, expected);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{SdfFloat, SdfGlyphError};
+
+/// Quantises signed distance field values (normalised to the radius, so in the range [-1, 1])
+/// for storage, and decodes them again.
+///
+/// All encodings map values inside the shape (negative distances) to high values, as is
+/// conventional for SDF glyphs. Values outside the range an encoding can represent are clamped.
+pub trait SdfEncoder {
+    /// The type of a single encoded value.
+    type Output: Copy;
+
+    /// Encodes a single value.
+    fn encode(&self, value: f64) -> Self::Output;
+
+    /// Decodes a single value. This is only accurate to within the precision of the encoding.
+    fn decode(&self, encoded: Self::Output) -> f64;
+
+    /// Encodes an entire signed distance field.
+    fn encode_sdf<F: SdfFloat>(&self, sdf: &[F]) -> Vec<Self::Output>
+    where
+        Self: Sized,
+    {
+        sdf.iter()
+            .map(|value| self.encode(value.to_f64()))
+            .collect()
+    }
+
+    /// Decodes an entire signed distance field.
+    fn decode_sdf(&self, encoded: &[Self::Output]) -> Vec<f64> {
+        encoded.iter().map(|value| self.decode(*value)).collect()
+    }
+}
+
+fn validate_cutoff(cutoff: f64) -> Result<(), SdfGlyphError> {
+    if cutoff <= 0.0 || cutoff >= 1.0 {
+        return Err(SdfGlyphError::InvalidCutoff(cutoff));
+    }
+    Ok(())
+}
+
+/// The standard linear byte encoding used by MapLibre and Mapbox GL
+/// (and [`clamp_to_u8`](crate::clamp_to_u8)).
+///
+/// The highest `cutoff` fraction of the byte range is used to encode values inside the glyph,
+/// and the rest for values outside it. This spends most of the range on the outside, which
+/// renderers need for halos.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearU8Encoder {
+    cutoff: f64,
+}
+
+impl LinearU8Encoder {
+    /// Creates a new encoder. The `cutoff` must be in the range (0, 1).
+    pub fn new(cutoff: f64) -> Result<Self, SdfGlyphError> {
+        validate_cutoff(cutoff)?;
+        Ok(Self { cutoff })
+    }
+}
+
+impl SdfEncoder for LinearU8Encoder {
+    type Output = u8;
+
+    fn encode(&self, value: f64) -> u8 {
+        // Note: casting from a float to an integer performs a saturating
+        // cast in Rust, removing the need for special logic.
+        // See https://doc.rust-lang.org/nomicon/casts.html.
+        (255.0 - 255.0 * (value + self.cutoff)) as u8
+    }
+
+    fn decode(&self, encoded: u8) -> f64 {
+        (255.0 - f64::from(encoded)) / 255.0 - self.cutoff
+    }
+}
+
+/// The same linear encoding as [`LinearU8Encoder`], but with 16 bits of precision.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearU16Encoder {
+    cutoff: f64,
+}
+
+impl LinearU16Encoder {
+    /// Creates a new encoder. The `cutoff` must be in the range (0, 1).
+    pub fn new(cutoff: f64) -> Result<Self, SdfGlyphError> {
+        validate_cutoff(cutoff)?;
+        Ok(Self { cutoff })
+    }
+}
+
+impl SdfEncoder for LinearU16Encoder {
+    type Output = u16;
+
+    fn encode(&self, value: f64) -> u16 {
+        (65535.0 - 65535.0 * (value + self.cutoff)).round() as u16
+    }
+
+    fn decode(&self, encoded: u16) -> f64 {
+        (65535.0 - f64::from(encoded)) / 65535.0 - self.cutoff
+    }
+}
+
+/// A linear byte encoding which represents the full range [-1, 1] symmetrically,
+/// with the outline in the middle of the byte range.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SymmetricU8Encoder;
+
+impl SdfEncoder for SymmetricU8Encoder {
+    type Output = u8;
+
+    fn encode(&self, value: f64) -> u8 {
+        ((1.0 - value) * 127.5).round() as u8
+    }
+
+    fn decode(&self, encoded: u8) -> f64 {
+        1.0 - f64::from(encoded) / 127.5
+    }
+}
+
+/// A symmetric byte encoding which applies a power curve to the distances, spending more
+/// of the byte range near the outline (where precision matters most for rendering sharp
+/// edges) and less on distant values.
+///
+/// Values are encoded as `sign(v) * |v|^(1 / gamma)`, so a `gamma` of 1 is the same as
+/// [`SymmetricU8Encoder`], and higher values put more precision near the outline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GammaU8Encoder {
+    gamma: f64,
+}
+
+impl GammaU8Encoder {
+    /// Creates a new encoder. The `gamma` must be positive.
+    pub fn new(gamma: f64) -> Result<Self, SdfGlyphError> {
+        if gamma <= 0.0 || !gamma.is_finite() {
+            return Err(SdfGlyphError::InvalidGamma(gamma));
+        }
+        Ok(Self { gamma })
+    }
+}
+
+impl SdfEncoder for GammaU8Encoder {
+    type Output = u8;
+
+    fn encode(&self, value: f64) -> u8 {
+        let value = value.clamp(-1.0, 1.0);
+        let shaped = value.signum() * value.abs().powf(self.gamma.recip());
+        ((1.0 - shaped) * 127.5).round() as u8
+    }
+
+    fn decode(&self, encoded: u8) -> f64 {
+        let shaped = 1.0 - f64::from(encoded) / 127.5;
+        shaped.signum() * shaped.abs().powf(self.gamma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        GammaU8Encoder, LinearU16Encoder, LinearU8Encoder, SdfEncoder, SymmetricU8Encoder,
+    };
+    use crate::clamp_to_u8;
+
+    /// Returns the maximum round trip error over a range of values.
+    fn max_error<E: SdfEncoder>(encoder: &E, min: f64, max: f64) -> f64 {
+        (0..=1000)
+            .map(|i| min + (max - min) * f64::from(i) / 1000.0)
+            .map(|value| (encoder.decode(encoder.encode(value)) - value).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_linear_u8() {
+        let sdf: Vec<f64> = Vec::from(include!("../fixtures/glyph_sdf_f64.json"));
+        let encoder = LinearU8Encoder::new(0.25).unwrap();
+        assert_eq!(encoder.encode_sdf(&sdf), clamp_to_u8(&sdf, 0.25).unwrap());
+        assert!(max_error(&encoder, -0.25, 0.75) <= 1.0 / 255.0);
+        assert!(LinearU8Encoder::new(0.0).is_err());
+    }
+
+    #[test]
+    fn test_linear_u16() {
+        let encoder = LinearU16Encoder::new(0.25).unwrap();
+        assert!(max_error(&encoder, -0.25, 0.75) <= 0.5 / 65535.0 + f64::EPSILON);
+        assert_eq!(encoder.encode(-1.0), u16::MAX);
+        assert_eq!(encoder.encode(1.0), 0);
+        assert!(LinearU16Encoder::new(1.0).is_err());
+    }
+
+    #[test]
+    fn test_symmetric() {
+        let encoder = SymmetricU8Encoder;
+        assert!(max_error(&encoder, -1.0, 1.0) <= 0.5 / 127.5 + f64::EPSILON);
+        assert_eq!(encoder.encode(-1.0), 255);
+        assert_eq!(encoder.encode(1.0), 0);
+    }
+
+    #[test]
+    fn test_gamma() {
+        let symmetric = SymmetricU8Encoder;
+        let gamma = GammaU8Encoder::new(2.0).unwrap();
+
+        // More precise near the edge, less so far away from it
+        assert!(max_error(&gamma, -0.04, 0.04) < max_error(&symmetric, -0.04, 0.04) / 2.0);
+        assert!(max_error(&gamma, 0.5, 1.0) > max_error(&symmetric, 0.5, 1.0));
+
+        assert_eq!(gamma.encode(-1.0), 255);
+        assert_eq!(gamma.encode(1.0), 0);
+        assert!(gamma.decode(gamma.encode(0.0)).abs() < 1e-4);
+
+        let linear_gamma = GammaU8Encoder::new(1.0).unwrap();
+        for value in [-1.0, -0.3, 0.0, 0.2, 0.9] {
+            assert_eq!(linear_gamma.encode(value), symmetric.encode(value));
+        }
+
+        assert!(GammaU8Encoder::new(0.0).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use thiserror::Error;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum SdfGlyphError {
     #[error("Missing size metrics")]
     MissingSizeMetrics,
@@ -8,9 +9,25 @@ pub enum SdfGlyphError {
     #[error("Invalid bitmap dimensions: The data length must be equal to {0} = {1}, but is equal to {2}.")]
     InvalidDataDimensions(&'static str, usize, usize),
 
+    #[error("Invalid stride: {0} is too small for rows of {1} bytes.")]
+    InvalidStride(isize, usize),
+
     #[error("Cutoff values must be between 0 and 1 (both non-inclusive), but {0} was provided.")]
     InvalidCutoff(f64),
 
+    #[error("Gamma values must be positive, but {0} was provided.")]
+    InvalidGamma(f64),
+
+    #[cfg(feature = "image")]
+    #[error(
+        "Unsupported image color type {0:?}: images must be grayscale or have an alpha channel."
+    )]
+    UnsupportedColorType(image::ColorType),
+
+    #[cfg(feature = "freetype")]
+    #[error("The face has no glyph for character code or glyph index {0}")]
+    MissingGlyph(u32),
+
     #[cfg(feature = "freetype")]
     #[error("FreeType error: {0}")]
     FreeTypeError(#[from] freetype::Error),
@@ -0,0 +1,103 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Sub};
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// A floating point type in which signed distance fields can be computed.
+///
+/// This is implemented for `f64` (the default everywhere) and `f32`. The final output of
+/// most SDF pipelines is quantised to a single byte (see [`clamp_to_u8`](crate::clamp_to_u8)),
+/// so `f32` is usually more than precise enough, while halving memory use.
+pub trait SdfFloat:
+    Copy
+    + 'static
+    + Send
+    + Sync
+    + Debug
+    + Default
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + private::Sealed
+{
+    const ZERO: Self;
+    const HALF: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn from_usize(value: usize) -> Self;
+
+    fn from_u8(value: u8) -> Self;
+
+    fn to_f64(self) -> f64;
+
+    #[must_use]
+    fn sqrt(self) -> Self;
+
+    #[must_use]
+    fn abs(self) -> Self;
+
+    #[must_use]
+    fn max(self, other: Self) -> Self;
+
+    #[must_use]
+    fn clamp(self, min: Self, max: Self) -> Self;
+}
+
+macro_rules! impl_sdf_float {
+    ($float:ty) => {
+        impl SdfFloat for $float {
+            const ZERO: Self = 0.0;
+            const HALF: Self = 0.5;
+            const ONE: Self = 1.0;
+            const MIN: Self = <$float>::MIN;
+            const MAX: Self = <$float>::MAX;
+
+            #[inline]
+            fn from_usize(value: usize) -> Self {
+                value as $float
+            }
+
+            #[inline]
+            fn from_u8(value: u8) -> Self {
+                Self::from(value)
+            }
+
+            #[inline]
+            fn to_f64(self) -> f64 {
+                f64::from(self)
+            }
+
+            #[inline]
+            fn sqrt(self) -> Self {
+                <$float>::sqrt(self)
+            }
+
+            #[inline]
+            fn abs(self) -> Self {
+                <$float>::abs(self)
+            }
+
+            #[inline]
+            fn max(self, other: Self) -> Self {
+                <$float>::max(self, other)
+            }
+
+            #[inline]
+            fn clamp(self, min: Self, max: Self) -> Self {
+                <$float>::clamp(self, min, max)
+            }
+        }
+    };
+}
+
+impl_sdf_float!(f32);
+impl_sdf_float!(f64);
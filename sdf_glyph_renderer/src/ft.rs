@@ -1,8 +1,12 @@
+use std::os::raw::c_long;
+
+use freetype::bitmap::PixelMode as FtPixelMode;
 use freetype::face::LoadFlag;
-use freetype::Face;
+use freetype::{ffi, Face, Matrix, RenderMode};
 
-use crate::{BitmapGlyph, SdfGlyphError};
+use crate::{BitmapGlyph, PixelMode, SdfGlyphError};
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct SdfGlyph {
     pub sdf: Vec<f64>,
     pub metrics: GlyphMetrics,
@@ -11,7 +15,13 @@ pub struct SdfGlyph {
 /// For an explanation of the technical terms used when describing the glyph metrics,
 /// the [FreeType tutorial](https://www.freetype.org/freetype2/docs/tutorial/step2.html) is a
 /// fantastic reference.
+///
+/// The fields are aligned to the pixel grid of the bitmap, with the advance rounded down and the
+/// ascender rounded up (following FreeType). The `precise_*` methods return the same metrics
+/// without rounding (to 1/64 px), so that callers encoding glyphs can choose their own
+/// rounding policy and layouts don't accumulate errors.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct GlyphMetrics {
     /// The unbuffered width of the glyph in px.
     pub width: usize,
@@ -34,49 +44,963 @@ pub struct GlyphMetrics {
 
     /// The typographical ascender in px.
     pub ascender: i32,
+
+    /// The unrounded metrics in 26.6 fixed point (1/64 px), as used by FreeType.
+    precise: PreciseMetrics,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PreciseMetrics {
+    left_bearing: i64,
+    top_bearing: i64,
+    h_advance: i64,
+    ascender: i64,
+}
+
+impl PreciseMetrics {
+    fn new(left_bearing: f64, top_bearing: f64, h_advance: f64, ascender: f64) -> Self {
+        let to_26_6 = |px: f64| (px * 64.0).round() as i64;
+        Self {
+            left_bearing: to_26_6(left_bearing),
+            top_bearing: to_26_6(top_bearing),
+            h_advance: to_26_6(h_advance),
+            ascender: to_26_6(ascender),
+        }
+    }
+}
+
+impl GlyphMetrics {
+    /// The left bearing of the glyph's outline in px, which may be fractional.
+    /// This can differ from [`left_bearing`](Self::left_bearing) by up to a pixel, since the
+    /// bitmap covers every pixel the outline touches.
+    #[must_use]
+    pub fn precise_left_bearing(&self) -> f64 {
+        self.precise.left_bearing as f64 / 64.0
+    }
+
+    /// The top bearing of the glyph's outline in px, which may be fractional.
+    #[must_use]
+    pub fn precise_top_bearing(&self) -> f64 {
+        self.precise.top_bearing as f64 / 64.0
+    }
+
+    /// The horizontal advance of the glyph in px, which may be fractional.
+    #[must_use]
+    pub fn precise_h_advance(&self) -> f64 {
+        self.precise.h_advance as f64 / 64.0
+    }
+
+    /// The typographical ascender in px, which may be fractional.
+    #[must_use]
+    pub fn precise_ascender(&self) -> f64 {
+        self.precise.ascender as f64 / 64.0
+    }
+}
+
+/// Synthetic styling applied to glyph outlines before rasterising,
+/// for faces which lack a real bold or italic cut.
+///
+/// The default leaves glyphs unchanged.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SyntheticStyle {
+    /// How much to thicken the outline by, as a fraction of the em size. The advance grows by
+    /// the same amount. FreeType's own synthetic bold ([`BOLD`](Self::BOLD)) uses 1/24.
+    pub embolden: f64,
+
+    /// The horizontal shear applied to the outline (the tangent of the slant angle).
+    /// FreeType's own synthetic oblique ([`OBLIQUE`](Self::OBLIQUE)) slants by about 12 degrees.
+    pub oblique: f64,
+}
+
+impl SyntheticStyle {
+    /// FreeType's default synthetic bold.
+    pub const BOLD: Self = Self {
+        embolden: 1.0 / 24.0,
+        oblique: 0.0,
+    };
+
+    /// FreeType's default synthetic oblique.
+    pub const OBLIQUE: Self = Self {
+        embolden: 0.0,
+        oblique: 0x0366A as f64 / 65536.0,
+    };
+
+    /// Returns the name of the style this derives from the original ("Bold", "Oblique" or
+    /// "Bold Oblique"), or `None` if glyphs are left unchanged.
+    #[must_use]
+    pub fn style_name(&self) -> Option<&'static str> {
+        match (self.embolden != 0.0, self.oblique != 0.0) {
+            (false, false) => None,
+            (true, false) => Some("Bold"),
+            (false, true) => Some("Oblique"),
+            (true, true) => Some("Bold Oblique"),
+        }
+    }
+}
+
+/// Options for rendering glyphs from a FreeType face with
+/// [`render_sdf_from_face_with_options`] and [`render_sdf_from_glyph_index_with_options`].
+///
+/// The default renders glyphs as they are, like [`render_sdf_from_face`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphRenderOptions {
+    style: SyntheticStyle,
+    bitmap_scale: f64,
+    trim: bool,
+    supersampling: usize,
+}
+
+impl Default for GlyphRenderOptions {
+    fn default() -> Self {
+        Self {
+            style: SyntheticStyle::default(),
+            bitmap_scale: 1.0,
+            trim: false,
+            supersampling: 1,
+        }
+    }
+}
+
+impl GlyphRenderOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies a [`SyntheticStyle`] to outline glyphs.
+    #[must_use]
+    pub fn with_style(mut self, style: SyntheticStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Scales the rasterised glyph (and its metrics) by `bitmap_scale`.
+    ///
+    /// This is needed for bitmap-only faces, whose glyphs only come in the sizes of their
+    /// strikes. Pass the scale returned by [`set_pixel_size`], which is always 1 for scalable
+    /// faces.
+    #[must_use]
+    pub fn with_bitmap_scale(mut self, bitmap_scale: f64) -> Self {
+        self.bitmap_scale = bitmap_scale;
+        self
+    }
+
+    /// Shrinks the bitmap to its non-zero coverage before the field is computed
+    /// (see [`BitmapGlyph::trim`]), with the bearings adjusted to match. Blank glyphs such as
+    /// spaces then have an empty field and a size of zero, but keep the rest of their metrics.
+    #[must_use]
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Supersamples outline glyphs by a whole `factor` for higher quality.
+    ///
+    /// The glyph is rasterised at `factor` times the face's current size, and the signed
+    /// distance field is computed at that resolution (with the buffer and radius scaled to
+    /// match). The field is then resampled to the pixel grid of the glyph at its normal size,
+    /// so the output (including the metrics) is a drop-in replacement for the glyph rendered
+    /// without supersampling.
+    ///
+    /// This is roughly `factor²` times slower, but captures thin strokes and sharp corners far
+    /// more accurately than the antialiased coverage of a single pixel can. A `factor` of 1
+    /// (or 0) turns supersampling off. Bitmap glyphs can't be rasterised at a higher
+    /// resolution, so they are always rendered without supersampling.
+    #[must_use]
+    pub fn with_supersampling(mut self, factor: usize) -> Self {
+        self.supersampling = factor;
+        self
+    }
 }
 
 /// This is a convenient frontend to [`render_sdf`](BitmapGlyph::render_sdf) that accepts a FreeType
 /// face as input and generates bitmaps automatically using the font's embedded metrics.
+///
+/// Returns [`SdfGlyphError::MissingGlyph`] if the face has no glyph for `char_code`.
 pub fn render_sdf_from_face(
     face: &Face,
     char_code: u32,
     buffer: usize,
     radius: usize,
 ) -> Result<SdfGlyph, SdfGlyphError> {
-    let ascender = (face
+    render_sdf_from_face_with_options(face, char_code, buffer, radius, &GlyphRenderOptions::new())
+}
+
+/// Renders a glyph like [`render_sdf_from_face`], with a [`SyntheticStyle`] applied.
+pub fn render_sdf_from_face_with_style(
+    face: &Face,
+    char_code: u32,
+    buffer: usize,
+    radius: usize,
+    style: SyntheticStyle,
+) -> Result<SdfGlyph, SdfGlyphError> {
+    let options = GlyphRenderOptions::new().with_style(style);
+    render_sdf_from_face_with_options(face, char_code, buffer, radius, &options)
+}
+
+/// Renders a glyph like [`render_sdf_from_face`], with the given [`GlyphRenderOptions`].
+pub fn render_sdf_from_face_with_options(
+    face: &Face,
+    char_code: u32,
+    buffer: usize,
+    radius: usize,
+    options: &GlyphRenderOptions,
+) -> Result<SdfGlyph, SdfGlyphError> {
+    render_sdf_from_glyph_index_with_options(
+        face,
+        glyph_index(face, char_code)?,
+        buffer,
+        radius,
+        options,
+    )
+}
+
+/// Renders a glyph like [`render_sdf_from_face`], but looks it up by its index in the face
+/// rather than by character code.
+///
+/// This is useful for glyphs which have no character code of their own, such as the output of
+/// a shaper, or icon fonts. Returns [`SdfGlyphError::MissingGlyph`] if `glyph_index` is out of
+/// range for the face.
+pub fn render_sdf_from_glyph_index(
+    face: &Face,
+    glyph_index: u32,
+    buffer: usize,
+    radius: usize,
+) -> Result<SdfGlyph, SdfGlyphError> {
+    render_sdf_from_glyph_index_with_options(
+        face,
+        glyph_index,
+        buffer,
+        radius,
+        &GlyphRenderOptions::new(),
+    )
+}
+
+/// Renders a glyph like [`render_sdf_from_glyph_index`], with a [`SyntheticStyle`] applied.
+pub fn render_sdf_from_glyph_index_with_style(
+    face: &Face,
+    glyph_index: u32,
+    buffer: usize,
+    radius: usize,
+    style: SyntheticStyle,
+) -> Result<SdfGlyph, SdfGlyphError> {
+    let options = GlyphRenderOptions::new().with_style(style);
+    render_sdf_from_glyph_index_with_options(face, glyph_index, buffer, radius, &options)
+}
+
+/// Renders a glyph like [`render_sdf_from_glyph_index`], with the given [`GlyphRenderOptions`].
+pub fn render_sdf_from_glyph_index_with_options(
+    face: &Face,
+    glyph_index: u32,
+    buffer: usize,
+    radius: usize,
+    options: &GlyphRenderOptions,
+) -> Result<SdfGlyph, SdfGlyphError> {
+    if glyph_index as ffi::FT_Long >= face.num_glyphs() {
+        return Err(SdfGlyphError::MissingGlyph(glyph_index));
+    }
+    let ascender = ascender(face)?.scale(options.bitmap_scale);
+    let rendered = render_bitmap(
+        face,
+        glyph_index,
+        buffer,
+        ascender,
+        OutlineAdjustment::new(1, options.style),
+        options.bitmap_scale,
+        options.trim,
+    )?;
+    if options.supersampling <= 1 || !rendered.outline {
+        return Ok(SdfGlyph {
+            sdf: rendered.bitmap.render_sdf(radius),
+            metrics: rendered.metrics,
+        });
+    }
+
+    // The glyph at its normal size determines the output grid and metrics
+    let RenderedBitmap {
+        bitmap, metrics, ..
+    } = rendered;
+    let factor = options.supersampling;
+
+    // The high resolution buffer has an extra (scaled) pixel on each side, since the
+    // bounding box of the scaled glyph does not always line up exactly.
+    let high_res = render_bitmap(
+        face,
+        glyph_index,
+        (buffer + 1) * factor,
+        ascender,
+        OutlineAdjustment::new(factor, options.style),
+        options.bitmap_scale,
+        false,
+    )?;
+    let (high_res_bitmap, high_res_metrics) = (high_res.bitmap, high_res.metrics);
+
+    // Distances are normalised to the radius, so the scaled field can be sampled directly
+    let high_res_sdf = high_res_bitmap.render_sdf(radius * factor);
+    let high_res_buffer = high_res_bitmap.buffer as f64;
+    let high_res_width = high_res_bitmap.width + high_res_bitmap.buffer * 2;
+    let high_res_height = high_res_bitmap.height + high_res_bitmap.buffer * 2;
+
+    let buffered_width = bitmap.width + buffer * 2;
+    let factor = factor as f64;
+    let sdf = (0..bitmap.alpha.len())
+        .map(|i| {
+            // The centre of the output pixel relative to the glyph origin (y up)
+            let x =
+                f64::from(metrics.left_bearing) + (i % buffered_width) as f64 - buffer as f64 + 0.5;
+            let y =
+                f64::from(metrics.top_bearing) - (i / buffered_width) as f64 + buffer as f64 - 0.5;
+
+            // ...and the same point in the high resolution grid (pixel centres at integers)
+            let high_res_x =
+                x * factor - f64::from(high_res_metrics.left_bearing) + high_res_buffer - 0.5;
+            let high_res_y =
+                f64::from(high_res_metrics.top_bearing) - y * factor + high_res_buffer - 0.5;
+
+            sample_bilinear(
+                &high_res_sdf,
+                high_res_width,
+                high_res_height,
+                high_res_x,
+                high_res_y,
+            )
+        })
+        .collect();
+
+    Ok(SdfGlyph { sdf, metrics })
+}
+
+/// Sets the size of a face in (possibly fractional) pixels per em, returning the scale to
+/// render its glyphs with (see [`GlyphRenderOptions::with_bitmap_scale`]).
+///
+/// Scalable faces are simply set to `size`, and the scale is 1. Bitmap-only faces, such as
+/// colour emoji fonts (CBDT/sbix) and legacy bitmap fonts, only come in a fixed set of sizes
+/// (strikes). For those, the smallest strike at least as large as `size` is selected (or the
+/// largest, if there is none), and the scale maps it to `size`.
+///
+/// Clones of a [`Face`] share the underlying FreeType face, so setting the size of a clone also
+/// sets it for the original.
+pub fn set_pixel_size(face: &mut Face, size: f64) -> Result<f64, SdfGlyphError> {
+    // Sizes are in 26.6 fixed point
+    let target = (size * 64.0).round() as ffi::FT_Pos;
+    let raw_face = face.raw();
+    if face.is_scalable() || raw_face.num_fixed_sizes <= 0 {
+        // FreeType conventions: char width or height of zero means "use the same value"
+        // and setting both resolution values to zero results in the default value
+        // of 72 dpi.
+        face.set_char_size(0, target as isize, 0, 0)?;
+        return Ok(1.0);
+    }
+
+    // SAFETY: FreeType guarantees `available_sizes` holds `num_fixed_sizes` strikes
+    let strikes = unsafe {
+        std::slice::from_raw_parts(raw_face.available_sizes, raw_face.num_fixed_sizes as usize)
+    };
+    let (strike_index, strike_ppem) = strikes
+        .iter()
+        .enumerate()
+        .filter(|(_, strike)| strike.y_ppem >= target)
+        .min_by_key(|(_, strike)| strike.y_ppem)
+        .or_else(|| {
+            strikes
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, strike)| strike.y_ppem)
+        })
+        .map(|(strike_index, strike)| (strike_index, strike.y_ppem))
+        .expect("There is at least one strike");
+
+    // SAFETY: the face is valid for the duration of the borrow, and the index is in range
+    let error = unsafe {
+        ffi::FT_Select_Size(
+            std::ptr::from_mut(face.raw_mut()),
+            strike_index as ffi::FT_Int,
+        )
+    };
+    if error != 0 {
+        return Err(freetype::Error::from(error).into());
+    }
+
+    Ok(target as f64 / strike_ppem as f64)
+}
+
+/// Returns the typographical ascender of the face at its current size in px.
+///
+/// FreeType rounds the ascender in the size metrics of scalable faces up to a whole pixel,
+/// so the precise value is scaled from the face's own ascender instead.
+fn ascender(face: &Face) -> Result<Ascender, SdfGlyphError> {
+    let size_metrics = face
         .size_metrics()
-        .ok_or(SdfGlyphError::MissingSizeMetrics)?
-        .ascender
-        >> 6) as i32;
+        .ok_or(SdfGlyphError::MissingSizeMetrics)?;
+    let precise = if face.is_scalable() {
+        f64::from(face.ascender()) * size_metrics.y_scale as f64 / 65536.0 / 64.0
+    } else {
+        size_metrics.ascender as f64 / 64.0
+    };
 
+    Ok(Ascender {
+        rounded: (size_metrics.ascender >> 6) as i32,
+        precise,
+    })
+}
+
+/// The typographical ascender of a face in px, both as reported by FreeType and without rounding.
+#[derive(Clone, Copy)]
+struct Ascender {
+    rounded: i32,
+    precise: f64,
+}
+
+impl Ascender {
+    fn scale(self, scale: f64) -> Self {
+        Self {
+            rounded: (f64::from(self.rounded) * scale).round() as i32,
+            precise: self.precise * scale,
+        }
+    }
+}
+
+fn glyph_index(face: &Face, char_code: u32) -> Result<u32, SdfGlyphError> {
     let Ok(glyph_index) = face.get_char_index(char_code as usize) else {
-        return Err(SdfGlyphError::FreeTypeError(
-            freetype::Error::InvalidGlyphIndex,
-        ));
+        return Err(SdfGlyphError::MissingGlyph(char_code));
     };
 
-    face.load_glyph(glyph_index.get(), LoadFlag::NO_HINTING | LoadFlag::RENDER)?;
+    Ok(glyph_index.get())
+}
+
+/// Changes made to an outline glyph between loading and rasterising it.
+///
+/// These are applied to the outline itself rather than set on the face with
+/// `FT_Set_Transform`, so any transform the caller has set on the face is kept
+/// (and applied first).
+#[derive(Clone, Copy, Debug, Default)]
+struct OutlineAdjustment {
+    /// A scale followed by a horizontal shear, or `None` to leave the outline as it is.
+    matrix: Option<Matrix>,
+
+    /// See [`SyntheticStyle::embolden`], but relative to the em size of the face before the
+    /// outline is scaled.
+    embolden: f64,
+}
+
+impl OutlineAdjustment {
+    /// Scales outlines by a whole `factor`, then applies the synthetic `style`.
+    fn new(factor: usize, style: SyntheticStyle) -> Self {
+        // The matrix is in 16.16 fixed point, and the shear applies to the scaled outline
+        let scale = (factor as c_long) << 16;
+        let shear = (style.oblique * factor as f64 * 65536.0).round() as c_long;
+        let matrix = (scale != 1 << 16 || shear != 0).then_some(Matrix {
+            xx: scale,
+            xy: shear,
+            yx: 0,
+            yy: scale,
+        });
+
+        Self {
+            matrix,
+            embolden: style.embolden * factor as f64,
+        }
+    }
+}
+
+/// A glyph rasterised by [`render_bitmap`].
+struct RenderedBitmap {
+    /// The buffered bitmap.
+    bitmap: BitmapGlyph,
+    metrics: GlyphMetrics,
 
+    /// Whether the glyph was rasterised from an outline, rather than loaded as a bitmap.
+    outline: bool,
+}
+
+/// Rasterises a glyph with the face's current size and transform.
+///
+/// Outline glyphs are transformed and then emboldened as set out in `adjustment` first,
+/// and the bitmap is trimmed to its coverage last if `trim` is set.
+fn render_bitmap(
+    face: &Face,
+    glyph_index: u32,
+    buffer: usize,
+    ascender: Ascender,
+    adjustment: OutlineAdjustment,
+    scale: f64,
+    trim: bool,
+) -> Result<RenderedBitmap, SdfGlyphError> {
+    // Colour glyphs are loaded as colour bitmaps, rather than (possibly blank) outlines
+    let load_flags = LoadFlag::NO_HINTING | LoadFlag::COLOR;
     let glyph = face.glyph();
+    let embolden = adjustment.embolden;
+    let mut strength = 0;
+    face.load_glyph(glyph_index, load_flags)?;
+    let outline = glyph.raw().format == ffi::FT_GLYPH_FORMAT_OUTLINE;
+    if outline {
+        if let Some(matrix) = adjustment.matrix {
+            // SAFETY: the glyph slot belongs to the face, and holds the outline just loaded
+            unsafe {
+                ffi::FT_Outline_Transform(
+                    std::ptr::addr_of_mut!((*face.raw().glyph).outline),
+                    &matrix,
+                );
+            }
+        }
+        if embolden != 0.0 {
+            // Like FT_GlyphSlot_Embolden, but with a configurable strength
+            let y_scale = face
+                .size_metrics()
+                .ok_or(SdfGlyphError::MissingSizeMetrics)?
+                .y_scale;
+            let em_size = f64::from(face.raw().units_per_EM) * y_scale as f64 / 65536.0;
+            strength = (em_size * embolden).round() as ffi::FT_Pos;
+
+            // SAFETY: the glyph slot belongs to the face, and holds the outline just loaded
+            let error =
+                unsafe { ffi::FT_Outline_Embolden(&mut (*face.raw().glyph).outline, strength) };
+            if error != 0 {
+                return Err(freetype::Error::from(error).into());
+            }
+        }
+    }
+    glyph.render_glyph(RenderMode::Normal)?;
+
     let glyph_bitmap = glyph.bitmap();
-    let bitmap = BitmapGlyph::from_unbuffered(
-        glyph_bitmap.buffer(),
-        glyph_bitmap.width() as usize,
-        glyph_bitmap.rows() as usize,
-        buffer,
-    )?;
+    let pixel_mode = match glyph_bitmap.pixel_mode()? {
+        FtPixelMode::Mono => PixelMode::Mono,
+        FtPixelMode::Gray => PixelMode::Gray,
+        FtPixelMode::Gray2 => PixelMode::Gray2,
+        FtPixelMode::Gray4 => PixelMode::Gray4,
+        FtPixelMode::Lcd => PixelMode::Lcd,
+        FtPixelMode::LcdV => PixelMode::LcdV,
+        FtPixelMode::Bgra => PixelMode::Bgra,
+        FtPixelMode::None => return Err(freetype::Error::UnexpectedPixelMode.into()),
+    };
+    // LCD bitmaps are three times as wide (or tall) as the glyph
+    let mut width = glyph_bitmap.width() as usize;
+    let mut height = glyph_bitmap.rows() as usize;
+    match pixel_mode {
+        PixelMode::Lcd => width /= 3,
+        PixelMode::LcdV => height /= 3,
+        _ => {}
+    }
+    let stride = glyph_bitmap.pitch() as isize;
+
+    let mut left_bearing = glyph.bitmap_left();
+    let mut top_bearing = glyph.bitmap_top();
+    // Like FT_GlyphSlot_Embolden, the outline grows upwards and to the right
+    let glyph_metrics = glyph.metrics();
+    let mut precise_left_bearing = glyph_metrics.horiBearingX as f64 / 64.0;
+    let mut precise_top_bearing = (glyph_metrics.horiBearingY + strength) as f64 / 64.0;
+    let mut precise_h_advance = (glyph_metrics.horiAdvance + strength).max(0) as f64 / 64.0;
+    let mut bitmap = if scale == 1.0 {
+        BitmapGlyph::from_pixels(
+            glyph_bitmap.buffer(),
+            width,
+            height,
+            stride,
+            pixel_mode,
+            buffer,
+        )?
+    } else {
+        let unscaled =
+            BitmapGlyph::from_pixels(glyph_bitmap.buffer(), width, height, stride, pixel_mode, 0)?;
+        let alpha;
+        (alpha, width, height) = resample(unscaled.alpha(), width, height, scale);
+        left_bearing = (f64::from(left_bearing) * scale).round() as i32;
+        top_bearing = (f64::from(top_bearing) * scale).round() as i32;
+        precise_left_bearing *= scale;
+        precise_top_bearing *= scale;
+        precise_h_advance *= scale;
+        BitmapGlyph::from_unbuffered(&alpha, width, height, buffer)?
+    };
+    if trim {
+        let (left, top) = bitmap.trim();
+        width = bitmap.width;
+        height = bitmap.height;
+        left_bearing += left as i32;
+        top_bearing -= top as i32;
+    }
+
     let metrics = GlyphMetrics {
-        width: bitmap.width,
-        height: bitmap.height,
-        left_bearing: glyph.bitmap_left(),
-        top_bearing: glyph.bitmap_top(),
-        h_advance: (glyph.metrics().horiAdvance >> 6) as u32,
-        ascender,
+        width,
+        height,
+        left_bearing,
+        top_bearing,
+        h_advance: precise_h_advance as u32,
+        ascender: ascender.rounded,
+        precise: PreciseMetrics::new(
+            precise_left_bearing,
+            precise_top_bearing,
+            precise_h_advance,
+            ascender.precise,
+        ),
     };
 
-    Ok(SdfGlyph {
-        sdf: bitmap.render_sdf(radius),
+    Ok(RenderedBitmap {
+        bitmap,
         metrics,
+        outline,
     })
 }
+
+/// Resamples an alpha bitmap by `scale` with a box filter, returning the new bitmap
+/// and its dimensions.
+fn resample(alpha: &[u8], width: usize, height: usize, scale: f64) -> (Vec<u8>, usize, usize) {
+    let new_width = (width as f64 * scale).round() as usize;
+    let new_height = (height as f64 * scale).round() as usize;
+    if new_width == 0 || new_height == 0 {
+        return (Vec::new(), 0, 0);
+    }
+
+    // The (fractional) overlap of each source pixel with each destination pixel along an axis
+    let weights = |size: usize, new_size: usize| -> Vec<Vec<(usize, f64)>> {
+        let step = size as f64 / new_size as f64;
+        (0..new_size)
+            .map(|i| {
+                let (start, end) = (i as f64 * step, (i + 1) as f64 * step);
+                (start.floor() as usize..(end.ceil() as usize).min(size))
+                    .map(|j| (j, (end.min((j + 1) as f64) - start.max(j as f64)) / step))
+                    .collect()
+            })
+            .collect()
+    };
+    let x_weights = weights(width, new_width);
+    let y_weights = weights(height, new_height);
+
+    let mut resampled = Vec::with_capacity(new_width * new_height);
+    for y_weights in &y_weights {
+        for x_weights in &x_weights {
+            let mut sum = 0.0;
+            for &(y, y_weight) in y_weights {
+                for &(x, x_weight) in x_weights {
+                    sum += f64::from(alpha[y * width + x]) * x_weight * y_weight;
+                }
+            }
+            resampled.push(sum.round().clamp(0.0, 255.0) as u8);
+        }
+    }
+
+    (resampled, new_width, new_height)
+}
+
+/// Samples a grid at a fractional position by bilinear interpolation,
+/// clamping positions outside the grid to its edges.
+fn sample_bilinear(grid: &[f64], width: usize, height: usize, x: f64, y: f64) -> f64 {
+    let x = x.clamp(0.0, (width - 1) as f64);
+    let y = y.clamp(0.0, (height - 1) as f64);
+    let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (tx, ty) = (x - x0 as f64, y - y0 as f64);
+
+    let top = grid[y0 * width + x0] * (1.0 - tx) + grid[y0 * width + x1] * tx;
+    let bottom = grid[y1 * width + x0] * (1.0 - tx) + grid[y1 * width + x1] * tx;
+    top * (1.0 - ty) + bottom * ty
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use freetype::{Face, Library, Matrix, Vector};
+
+    use super::{
+        render_sdf_from_face, render_sdf_from_face_with_options, render_sdf_from_face_with_style,
+        render_sdf_from_glyph_index, render_sdf_from_glyph_index_with_options, resample,
+        set_pixel_size, GlyphRenderOptions, SyntheticStyle,
+    };
+    use crate::SdfGlyphError;
+
+    /// Loads the Open Sans Light font from the `pbf_font_tools` test fixtures.
+    fn open_sans_light() -> Face {
+        let font_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("pbf_font_tools")
+            .join("tests")
+            .join("glyphs")
+            .join("Open Sans Light")
+            .join("Open Sans Light.ttf");
+        Library::init().unwrap().new_face(font_path, 0).unwrap()
+    }
+
+    /// Loads the bitmap-only fixture font, which has a single 16px strike.
+    fn bitmap_font() -> Face {
+        let font_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join("bitmap_font.bdf");
+        Library::init().unwrap().new_face(font_path, 0).unwrap()
+    }
+
+    #[test]
+    fn test_supersampled_glyph() {
+        let face = open_sans_light();
+        face.set_char_size(0, 14 * 64, 0, 0).unwrap();
+        let ampersand = render_sdf_from_face(&face, '&' as u32, 3, 8).unwrap();
+        let render = |char_code: u32, options: GlyphRenderOptions| {
+            render_sdf_from_face_with_options(&face, char_code, 3, 8, &options).unwrap()
+        };
+
+        for char_code in ['&', 'i', ' '] {
+            let char_code = char_code as u32;
+            let glyph = render_sdf_from_face(&face, char_code, 3, 8).unwrap();
+            let options = GlyphRenderOptions::new();
+            assert_eq!(render(char_code, options.with_supersampling(1)), glyph);
+
+            // A heavily supersampled glyph is the reference for measuring quality
+            let reference = render(char_code, options.with_supersampling(16));
+            let supersampled = render(char_code, options.with_supersampling(4));
+            assert_eq!(supersampled.metrics, glyph.metrics);
+            assert_eq!(reference.metrics, glyph.metrics);
+            assert_eq!(supersampled.sdf.len(), glyph.sdf.len());
+
+            let mean_error = |sdf: &[f64]| {
+                sdf.iter()
+                    .zip(reference.sdf.iter())
+                    .map(|(value, expected)| (value - expected).abs())
+                    .sum::<f64>()
+                    / sdf.len().max(1) as f64
+            };
+            assert!(mean_error(&supersampled.sdf) <= mean_error(&glyph.sdf));
+        }
+
+        // Styled and trimmed glyphs keep the metrics of the glyph at the target size
+        let style = SyntheticStyle {
+            embolden: SyntheticStyle::BOLD.embolden,
+            oblique: SyntheticStyle::OBLIQUE.oblique,
+        };
+        for options in [
+            GlyphRenderOptions::new().with_style(style),
+            GlyphRenderOptions::new().with_trim(true),
+        ] {
+            for char_code in ['&', 'l', ' '] {
+                let glyph = render(char_code as u32, options);
+                let supersampled = render(char_code as u32, options.with_supersampling(4));
+                assert_eq!(supersampled.metrics, glyph.metrics);
+                assert_eq!(supersampled.sdf.len(), glyph.sdf.len());
+
+                // The style is applied at the higher resolution too, so the glyphs cover
+                // (almost) the same pixels
+                let inside = |sdf: &[f64]| sdf.iter().filter(|&&value| value < 0.0).count();
+                assert!(inside(&supersampled.sdf).abs_diff(inside(&glyph.sdf)) <= 3);
+            }
+        }
+
+        // The face must be left untransformed
+        assert_eq!(
+            render_sdf_from_face(&face, '&' as u32, 3, 8).unwrap(),
+            ampersand
+        );
+
+        // Bitmap glyphs are rendered without supersampling, at the strike's scale
+        let face = bitmap_font();
+        let scale = set_pixel_size(&mut face.clone(), 24.0).unwrap();
+        let options = GlyphRenderOptions::new().with_bitmap_scale(scale);
+        let glyph = render_sdf_from_face_with_options(&face, '#' as u32, 3, 8, &options).unwrap();
+        assert_eq!(glyph.metrics.width, 15);
+        assert_eq!(
+            render_sdf_from_face_with_options(
+                &face,
+                '#' as u32,
+                3,
+                8,
+                &options.with_supersampling(4)
+            )
+            .unwrap(),
+            glyph
+        );
+    }
+
+    #[test]
+    fn test_synthetic_style() {
+        let face = open_sans_light();
+        face.set_char_size(0, 48 * 64, 0, 0).unwrap();
+        let render = |style| render_sdf_from_face_with_style(&face, 'l' as u32, 3, 8, style);
+
+        let regular = render_sdf_from_face(&face, 'l' as u32, 3, 8).unwrap();
+        assert_eq!(render(SyntheticStyle::default()).unwrap(), regular);
+        assert_eq!(SyntheticStyle::default().style_name(), None);
+
+        // 1/24 of 48px thickens the stem by 2px, and the advance with it
+        let bold = render(SyntheticStyle::BOLD).unwrap();
+        assert_eq!(SyntheticStyle::BOLD.style_name(), Some("Bold"));
+        assert_eq!(bold.metrics.h_advance, regular.metrics.h_advance + 2);
+        assert_eq!(bold.metrics.width, regular.metrics.width + 2);
+        let inside = |sdf: &[f64]| sdf.iter().filter(|&&value| value < 0.0).count();
+        assert!(inside(&bold.sdf) > inside(&regular.sdf));
+
+        // Slanting the stem widens its bounding box, but leaves the advance alone
+        let oblique = render(SyntheticStyle::OBLIQUE).unwrap();
+        assert_eq!(SyntheticStyle::OBLIQUE.style_name(), Some("Oblique"));
+        assert_eq!(oblique.metrics.h_advance, regular.metrics.h_advance);
+        assert_eq!(oblique.metrics.height, regular.metrics.height);
+        assert!(oblique.metrics.width > regular.metrics.width + 5);
+
+        // The face must be left untransformed
+        assert_eq!(
+            render_sdf_from_face(&face, 'l' as u32, 3, 8).unwrap(),
+            regular
+        );
+
+        // ...and a transform set by the caller is kept, and applied to styled glyphs too
+        face.set_transform(
+            &mut Matrix {
+                xx: 1 << 16,
+                xy: 0,
+                yx: 0,
+                yy: 2 << 16,
+            },
+            &mut Vector { x: 0, y: 0 },
+        );
+        let tall = render_sdf_from_face(&face, 'l' as u32, 3, 8).unwrap();
+        assert!(tall.metrics.height > regular.metrics.height * 2 - 2);
+        let tall_oblique = render(SyntheticStyle::OBLIQUE).unwrap();
+        assert!(tall_oblique.metrics.height > regular.metrics.height * 2 - 2);
+        assert!(tall_oblique.metrics.width > oblique.metrics.width);
+        assert_eq!(render_sdf_from_face(&face, 'l' as u32, 3, 8).unwrap(), tall);
+    }
+
+    #[test]
+    fn test_glyph_index() {
+        let face = open_sans_light();
+        face.set_char_size(0, 24 * 64, 0, 0).unwrap();
+
+        let glyph_index = face.get_char_index('g' as usize).unwrap().get();
+        assert_eq!(
+            render_sdf_from_glyph_index(&face, glyph_index, 3, 8).unwrap(),
+            render_sdf_from_face(&face, 'g' as u32, 3, 8).unwrap()
+        );
+
+        let num_glyphs = face.num_glyphs() as u32;
+        assert!(matches!(
+            render_sdf_from_glyph_index(&face, num_glyphs, 3, 8),
+            Err(SdfGlyphError::MissingGlyph(index)) if index == num_glyphs
+        ));
+        assert!(matches!(
+            render_sdf_from_face(&face, 0x4E00, 3, 8),
+            Err(SdfGlyphError::MissingGlyph(0x4E00))
+        ));
+    }
+
+    #[test]
+    fn test_precise_metrics() {
+        let face = open_sans_light();
+        let render = |size: f64, char_code: char| {
+            assert_eq!(set_pixel_size(&mut face.clone(), size).unwrap(), 1.0);
+            render_sdf_from_face(&face, char_code as u32, 3, 8)
+                .unwrap()
+                .metrics
+        };
+
+        // Integer metrics are rounded down, and the precise ones keep the fraction
+        let metrics = render(14.0, 'a');
+        assert_eq!(
+            metrics.h_advance,
+            metrics.precise_h_advance().floor() as u32
+        );
+        assert_ne!(metrics.precise_h_advance().fract(), 0.0);
+        assert_eq!(metrics.ascender, metrics.precise_ascender().ceil() as i32);
+        assert_ne!(metrics.precise_ascender().fract(), 0.0);
+
+        // The bitmap covers the outline
+        assert!(f64::from(metrics.left_bearing) <= metrics.precise_left_bearing());
+        assert!(f64::from(metrics.top_bearing) >= metrics.precise_top_bearing());
+
+        // Fractional sizes scale the metrics (FreeType rounds advances to 1/64 px)
+        let fractional = render(14.5, 'a');
+        let expected = metrics.precise_h_advance() * 14.5 / 14.0;
+        assert!((fractional.precise_h_advance() - expected).abs() <= 1.0 / 64.0);
+        assert!(fractional.precise_ascender() > metrics.precise_ascender());
+    }
+
+    #[test]
+    fn test_bitmap_font() {
+        let face = bitmap_font();
+        assert!(!face.is_scalable());
+
+        // The only strike is 16px, which is scaled to the requested size
+        let glyph_index = face.get_char_index('#' as usize).unwrap().get();
+        let render = |size: f64| {
+            let scale = set_pixel_size(&mut face.clone(), size).unwrap();
+            let options = GlyphRenderOptions::new().with_bitmap_scale(scale);
+            render_sdf_from_glyph_index_with_options(&face, glyph_index, 3, 8, &options).unwrap()
+        };
+        let strike = render(16.0);
+        assert_eq!(strike.metrics.width, 10);
+        assert_eq!(strike.metrics.height, 10);
+        assert_eq!(strike.metrics.left_bearing, 1);
+        assert_eq!(strike.metrics.top_bearing, 10);
+        assert_eq!(strike.metrics.h_advance, 12);
+        assert_eq!(strike.metrics.ascender, 12);
+
+        // The mono bitmap (with padded rows) is fully inside at the centre and outside at the
+        // corners of the buffer
+        let buffered_width = 10 + 2 * 3;
+        assert!(strike.sdf[8 * buffered_width + 8] < 0.0);
+        assert!(strike.sdf[0] > 0.0);
+        assert_eq!(
+            render_sdf_from_face(&face, '#' as u32, 3, 8).unwrap(),
+            strike
+        );
+
+        let scaled = render(24.0);
+        assert_eq!(scaled.metrics.width, 15);
+        assert_eq!(scaled.metrics.height, 15);
+        assert_eq!(scaled.metrics.left_bearing, 2);
+        assert_eq!(scaled.metrics.top_bearing, 15);
+        assert_eq!(scaled.metrics.h_advance, 18);
+        assert_eq!(scaled.metrics.ascender, 18);
+
+        let space = face.get_char_index(' ' as usize).unwrap().get();
+        let scale = set_pixel_size(&mut face.clone(), 8.0).unwrap();
+        let options = GlyphRenderOptions::new().with_bitmap_scale(scale);
+        let space = render_sdf_from_glyph_index_with_options(&face, space, 3, 8, &options).unwrap();
+        assert_eq!((space.metrics.width, space.metrics.h_advance), (0, 4));
+    }
+
+    #[test]
+    fn test_trim() {
+        let mut face = bitmap_font();
+        let scale = set_pixel_size(&mut face, 16.0).unwrap();
+        let render = |char_code: char, trim: bool| {
+            let glyph_index = face.get_char_index(char_code as usize).unwrap().get();
+            let options = GlyphRenderOptions::new()
+                .with_bitmap_scale(scale)
+                .with_trim(trim);
+            render_sdf_from_glyph_index_with_options(&face, glyph_index, 3, 8, &options).unwrap()
+        };
+
+        // The underscore's bitmap has two transparent rows above the bar
+        let underscore = render('_', false);
+        let trimmed = render('_', true);
+        assert_eq!((underscore.metrics.height, trimmed.metrics.height), (4, 2));
+        assert_eq!(underscore.metrics.top_bearing, 2);
+        assert_eq!(trimmed.metrics.top_bearing, 0);
+        assert_eq!(
+            trimmed.metrics.left_bearing,
+            underscore.metrics.left_bearing
+        );
+        assert_eq!(trimmed.metrics.width, underscore.metrics.width);
+        assert_eq!(trimmed.metrics.h_advance, underscore.metrics.h_advance);
+        assert_eq!(trimmed.sdf.len(), 16 * 8);
+
+        // Tight glyphs are unchanged, and blank ones have no field at all
+        assert_eq!(render('#', true), render('#', false));
+        let space = render(' ', true);
+        assert!(space.sdf.is_empty());
+        assert_eq!((space.metrics.width, space.metrics.height), (0, 0));
+        assert_eq!(space.metrics.h_advance, 8);
+    }
+
+    #[test]
+    fn test_resample() {
+        let alpha = [0, 255, 255, 0, 255, 255, 0, 0, 0];
+        assert_eq!(resample(&alpha, 3, 3, 1.0), (alpha.to_vec(), 3, 3));
+
+        // Each 2x2 block is averaged
+        let alpha = [0, 255, 255, 255, 255, 255, 255, 255];
+        assert_eq!(resample(&alpha, 4, 2, 0.5), (vec![191, 255], 2, 1));
+
+        // ...and each pixel is repeated
+        assert_eq!(
+            resample(&[0, 255], 2, 1, 2.0),
+            (vec![0, 0, 255, 255, 0, 0, 255, 255], 4, 2)
+        );
+    }
+}
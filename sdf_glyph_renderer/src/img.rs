@@ -0,0 +1,150 @@
+use image::{DynamicImage, GrayImage, ImageBuffer, Luma};
+
+use crate::{BitmapGlyph, SdfFloat, SdfGlyphError};
+
+/// A single channel floating point image, as used for raw signed distance fields.
+pub type SdfImage = ImageBuffer<Luma<f32>, Vec<f32>>;
+
+/// Uses a grayscale image as the alpha channel, with no buffer.
+///
+/// To add a buffer, use [`BitmapGlyph::from_unbuffered`] with the raw image data instead.
+impl From<GrayImage> for BitmapGlyph {
+    fn from(image: GrayImage) -> Self {
+        BitmapGlyph {
+            width: image.width() as usize,
+            height: image.height() as usize,
+            alpha: image.into_raw(),
+            buffer: 0,
+            edge_seeding: Default::default(),
+        }
+    }
+}
+
+/// Converts an image with an alpha channel (using the alpha channel) or a grayscale image
+/// (using the luminance, as for a mask), with no buffer.
+///
+/// Colour images without an alpha channel are rejected, since it is ambiguous which part of
+/// the image is the shape.
+impl TryFrom<DynamicImage> for BitmapGlyph {
+    type Error = SdfGlyphError;
+
+    fn try_from(image: DynamicImage) -> Result<Self, Self::Error> {
+        let color = image.color();
+        if color.has_alpha() {
+            let image = image.into_luma_alpha8();
+            let (width, height) = image.dimensions();
+            let alpha = image.pixels().map(|pixel| pixel.0[1]).collect();
+            Ok(GrayImage::from_raw(width, height, alpha)
+                .expect("The buffer is always the right size")
+                .into())
+        } else if !color.has_color() {
+            Ok(image.into_luma8().into())
+        } else {
+            Err(SdfGlyphError::UnsupportedColorType(color))
+        }
+    }
+}
+
+impl BitmapGlyph {
+    /// Exports the (buffered) alpha bitmap as a grayscale image.
+    #[must_use]
+    pub fn to_image(&self) -> GrayImage {
+        let (width, height) = self.buffered_dimensions();
+        GrayImage::from_raw(width, height, self.alpha.clone())
+            .expect("The alpha bitmap always matches the dimensions")
+    }
+
+    /// Exports a signed distance field rendered from this bitmap as a floating point image.
+    pub fn sdf_to_image<F: SdfFloat>(&self, sdf: &[F]) -> Result<SdfImage, SdfGlyphError> {
+        self.check_len(sdf.len())?;
+        let (width, height) = self.buffered_dimensions();
+        Ok(SdfImage::from_raw(
+            width,
+            height,
+            sdf.iter().map(|value| value.to_f64() as f32).collect(),
+        )
+        .expect("The length has been checked"))
+    }
+
+    /// Exports an encoded signed distance field (e.g. from [`clamp_to_u8`](crate::clamp_to_u8))
+    /// rendered from this bitmap as a grayscale image.
+    pub fn encoded_sdf_to_image(&self, encoded: &[u8]) -> Result<GrayImage, SdfGlyphError> {
+        self.check_len(encoded.len())?;
+        let (width, height) = self.buffered_dimensions();
+        Ok(GrayImage::from_raw(width, height, encoded.to_vec())
+            .expect("The length has been checked"))
+    }
+
+    fn buffered_dimensions(&self) -> (u32, u32) {
+        (
+            (self.width + self.buffer * 2) as u32,
+            (self.height + self.buffer * 2) as u32,
+        )
+    }
+
+    fn check_len(&self, len: usize) -> Result<(), SdfGlyphError> {
+        if len != self.alpha.len() {
+            return Err(SdfGlyphError::InvalidDataDimensions(
+                "(width + buffer * 2) * (height + buffer * 2)",
+                self.alpha.len(),
+                len,
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, GrayImage, Luma, LumaA, Rgb, RgbImage, Rgba, RgbaImage};
+
+    use crate::{clamp_to_u8, BitmapGlyph};
+
+    #[test]
+    fn test_gray_image_round_trip() {
+        let image = GrayImage::from_fn(5, 3, |x, y| Luma([(x * 40 + y) as u8]));
+        let bitmap = BitmapGlyph::from(image.clone());
+
+        assert_eq!(bitmap.width(), 5);
+        assert_eq!(bitmap.height(), 3);
+        assert_eq!(bitmap.buffer(), 0);
+        assert_eq!(bitmap.alpha(), image.as_raw().as_slice());
+        assert_eq!(bitmap.to_image(), image);
+    }
+
+    #[test]
+    fn test_dynamic_image() {
+        let rgba = RgbaImage::from_fn(4, 4, |x, _| Rgba([255, 0, 0, (x * 60) as u8]));
+        let bitmap = BitmapGlyph::try_from(DynamicImage::ImageRgba8(rgba)).unwrap();
+        assert_eq!(&bitmap.alpha()[..4], &[0, 60, 120, 180]);
+
+        let luma_alpha = image::ImageBuffer::from_pixel(2, 2, LumaA([10u8, 200]));
+        let bitmap = BitmapGlyph::try_from(DynamicImage::ImageLumaA8(luma_alpha)).unwrap();
+        assert_eq!(bitmap.alpha(), &[200; 4]);
+
+        let luma = GrayImage::from_pixel(2, 2, Luma([77]));
+        let bitmap = BitmapGlyph::try_from(DynamicImage::ImageLuma8(luma)).unwrap();
+        assert_eq!(bitmap.alpha(), &[77; 4]);
+
+        let rgb = RgbImage::from_pixel(2, 2, Rgb([1, 2, 3]));
+        assert!(BitmapGlyph::try_from(DynamicImage::ImageRgb8(rgb)).is_err());
+    }
+
+    #[test]
+    fn test_sdf_images() {
+        let alpha = Vec::from(include!("../fixtures/glyph_alpha.json"));
+        let bitmap = BitmapGlyph::new(alpha, 16, 19, 3).unwrap();
+        let sdf = bitmap.render_sdf(8);
+
+        let sdf_image = bitmap.sdf_to_image(&sdf).unwrap();
+        assert_eq!(sdf_image.dimensions(), (22, 25));
+        assert_eq!(sdf_image.get_pixel(0, 0).0[0], sdf[0] as f32);
+
+        let encoded = clamp_to_u8(&sdf, 0.25).unwrap();
+        let encoded_image = bitmap.encoded_sdf_to_image(&encoded).unwrap();
+        assert_eq!(encoded_image.as_raw(), &encoded);
+
+        assert!(bitmap.sdf_to_image(&sdf[1..]).is_err());
+        assert!(bitmap.encoded_sdf_to_image(&encoded[1..]).is_err());
+    }
+}
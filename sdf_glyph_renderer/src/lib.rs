@@ -22,9 +22,30 @@
 mod core;
 pub use crate::core::*;
 
+mod encoding;
+pub use crate::encoding::*;
+
 mod error;
 pub use crate::error::SdfGlyphError;
 
+mod float;
+pub use crate::float::SdfFloat;
+
+#[cfg(feature = "image")]
+mod img;
+
+#[cfg(feature = "image")]
+pub use crate::img::SdfImage;
+
+// Re-export image crate if the feature is enabled
+#[cfg(feature = "image")]
+pub use image;
+
+pub mod transform;
+pub use crate::transform::{DistanceAlgorithm, DistanceTransform};
+
+pub mod edt;
+
 #[cfg(feature = "freetype")]
 mod ft;
 
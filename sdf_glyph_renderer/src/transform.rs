@@ -0,0 +1,712 @@
+//! Distance transform algorithms.
+//!
+//! All signed distance fields are computed from two squared Euclidean distance transforms
+//! (one for the outside of the shape and one for the inside). Several algorithms are available
+//! for this, which trade exactness for speed in different ways:
+//!
+//! | Algorithm                                   | Exact | Max error (px) | Mean error (px) | Relative time |
+//! |---------------------------------------------|-------|----------------|-----------------|---------------|
+//! | [`FelzenszwalbHuttenlocher`] (default)      | Yes   | < 1e-9         | < 1e-9          | 1×            |
+//! | [`Ssedt8`] (8-point sequential signed EDT)  | No    | < 0.05         | < 0.001         | ~2.3×         |
+//! | [`DeadReckoning`]                           | No    | < 0.5          | < 0.02          | ~1.1×         |
+//! | [`JumpFlooding`]                            | No    | < 0.05         | < 0.001         | ~15×          |
+//!
+//! The errors are the bounds asserted by the tests, which compare each algorithm against a
+//! brute force transform on a rendered glyph and on an irregular grid of scattered features.
+//! The times are relative to Felzenszwalb & Huttenlocher for rendering a glyph, as measured by
+//! the `distance transform` group of the benchmarks (`cargo bench -- "distance transform"`).
+//! Felzenszwalb & Huttenlocher is exact and already linear in the number of pixels,
+//! so it is a good choice unless you have measured otherwise.
+//! The approximate algorithms only ever consider the seeds found by propagating between
+//! neighbours, so they will occasionally settle on a seed which is not the nearest.
+
+use std::fmt::Debug;
+
+use crate::SdfFloat;
+
+/// A 2D squared Euclidean distance transform.
+///
+/// Implementations receive a row-major grid of `width` x `height` values, where each value is
+/// either the squared distance offset of a feature pixel (zero for pixels fully on the shape),
+/// or `F::MAX` for pixels which are not features. Every value is replaced in place with the
+/// (possibly approximate) squared distance to the nearest feature: the minimum over all
+/// features `q` of `|p - q|² + grid[q]`.
+///
+/// Implementations may keep scratch buffers between calls.
+pub trait DistanceTransform<F: SdfFloat>: Debug + Send {
+    fn transform(&mut self, grid: &mut [F], width: usize, height: usize);
+}
+
+/// The built-in distance transform algorithms.
+/// See the [module documentation](self) for a comparison.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DistanceAlgorithm {
+    /// The exact, linear time algorithm of Felzenszwalb & Huttenlocher.
+    #[default]
+    FelzenszwalbHuttenlocher,
+
+    /// The 8-point sequential signed Euclidean distance transform (8SSEDT).
+    Ssedt8,
+
+    /// The dead reckoning algorithm described by Grevera.
+    DeadReckoning,
+
+    /// The jump flooding algorithm described by Rong & Tan,
+    /// with an extra single pixel pass at the end (JFA+1).
+    JumpFlooding,
+}
+
+impl DistanceAlgorithm {
+    /// Creates a new instance of the algorithm.
+    #[must_use]
+    pub fn transform<F: SdfFloat>(self) -> Box<dyn DistanceTransform<F>> {
+        match self {
+            Self::FelzenszwalbHuttenlocher => Box::new(FelzenszwalbHuttenlocher::new()),
+            Self::Ssedt8 => Box::new(Ssedt8::new()),
+            Self::DeadReckoning => Box::new(DeadReckoning::new()),
+            Self::JumpFlooding => Box::new(JumpFlooding::new()),
+        }
+    }
+}
+
+/// The exact Euclidean distance transform described in
+/// [this paper](http://cs.brown.edu/people/pfelzens/papers/dt-final.pdf) by
+/// Felzenszwalb & Huttenlocher.
+///
+/// When the optional `rayon` feature is enabled, the row and column passes are spread across
+/// the rayon thread pool for large grids (glyphs are generally too small to benefit).
+#[derive(Debug)]
+pub struct FelzenszwalbHuttenlocher<F> {
+    transposed: Vec<F>,
+    scratch: DtScratch<F>,
+    #[cfg(feature = "rayon")]
+    parallel_threshold: usize,
+}
+
+/// The minimum number of pixels in a grid before the distance transform is parallelised.
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 128 * 128;
+
+impl<F: SdfFloat> Default for FelzenszwalbHuttenlocher<F> {
+    fn default() -> Self {
+        Self {
+            transposed: Vec::new(),
+            scratch: DtScratch::default(),
+            #[cfg(feature = "rayon")]
+            parallel_threshold: PARALLEL_THRESHOLD,
+        }
+    }
+}
+
+impl<F: SdfFloat> FelzenszwalbHuttenlocher<F> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn is_parallel(&self, len: usize) -> bool {
+        len >= self.parallel_threshold
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn is_parallel(&self, _len: usize) -> bool {
+        false
+    }
+}
+
+impl<F: SdfFloat> DistanceTransform<F> for FelzenszwalbHuttenlocher<F> {
+    fn transform(&mut self, grid: &mut [F], width: usize, height: usize) {
+        let parallel = self.is_parallel(grid.len());
+
+        // Per page 8 (422), the 2D distance transform can be obtained by computing the
+        // one-dimensional distance transform along each column first and then computing
+        // the transform along each row of the result.
+        //
+        // Walking down a column of a row-major grid is very cache (and SIMD) unfriendly, so we
+        // transpose the grid first. This lets the column pass operate on contiguous rows too.
+        self.transposed.resize(grid.len(), F::ZERO);
+        transpose(grid, &mut self.transposed, width, height);
+        transform_rows(&mut self.transposed, height, &mut self.scratch, parallel);
+        transpose(&self.transposed, grid, height, width);
+
+        transform_rows(grid, width, &mut self.scratch, parallel);
+    }
+}
+
+/// Transposes a row-major grid of `width` x `height` values into `dst`, which will have
+/// rows of length `height`.
+fn transpose<F: SdfFloat>(src: &[F], dst: &mut [F], width: usize, height: usize) {
+    // Working in small square blocks keeps both the reads and writes reasonably cache friendly.
+    const BLOCK_SIZE: usize = 16;
+
+    for block_y in (0..height).step_by(BLOCK_SIZE) {
+        for block_x in (0..width).step_by(BLOCK_SIZE) {
+            for y in block_y..(block_y + BLOCK_SIZE).min(height) {
+                for x in block_x..(block_x + BLOCK_SIZE).min(width) {
+                    dst[x * height + y] = src[y * width + x];
+                }
+            }
+        }
+    }
+}
+
+/// Runs the 1D distance transform over every row of a row-major grid, optionally spreading
+/// the rows across the rayon thread pool.
+#[cfg_attr(not(feature = "rayon"), allow(unused_variables))]
+fn transform_rows<F: SdfFloat>(
+    grid: &mut [F],
+    row_len: usize,
+    scratch: &mut DtScratch<F>,
+    parallel: bool,
+) {
+    if row_len == 0 {
+        return;
+    }
+
+    #[cfg(feature = "rayon")]
+    if parallel {
+        use rayon::prelude::*;
+
+        grid.par_chunks_exact_mut(row_len)
+            .for_each_init(DtScratch::default, |scratch, row| dt(row, scratch));
+        return;
+    }
+
+    for row in grid.chunks_exact_mut(row_len) {
+        dt(row, scratch);
+    }
+}
+
+/// Scratch space for [`dt`], which grows to fit the longest slice transformed so far.
+#[derive(Debug, Default)]
+pub(crate) struct DtScratch<F> {
+    f: Vec<F>,
+    v: Vec<usize>,
+    z: Vec<F>,
+}
+
+/// An O(n) Euclidean Distance Transform algorithm.
+/// See page 6 (420) of [paper](http://cs.brown.edu/people/pfelzens/papers/dt-final.pdf) for details and
+/// further discussion of the math behind this.
+fn dt<F: SdfFloat>(grid: &mut [F], scratch: &mut DtScratch<F>) {
+    dt_with_nearest(grid, scratch, |_, _| {});
+}
+
+/// Runs [`dt`], also passing each position and the position of its nearest feature
+/// (the minimum of the envelope) to `nearest`.
+pub(crate) fn dt_with_nearest<F: SdfFloat, N: FnMut(usize, usize)>(
+    grid: &mut [F],
+    scratch: &mut DtScratch<F>,
+    mut nearest: N,
+) {
+    let size = grid.len();
+    if size == 0 {
+        return;
+    }
+    if scratch.v.len() < size {
+        scratch.f.resize(size, F::ZERO);
+        scratch.v.resize(size, 0);
+        scratch.z.resize(size + 1, F::ZERO);
+    }
+    let DtScratch { f, v, z } = scratch;
+
+    // For our purposes, f is a one-dimensional slice of the grid
+    f[..size].copy_from_slice(grid);
+
+    // It may be possible to make this more functional in style,
+    // but for now this is more or less a "dumb" transcription of
+    // the algorithm presented in the paper by Felzenszwalb & Huttenlocher.
+    let mut k = 0;
+    let mut s: F;
+
+    v[0] = 0;
+    z[0] = F::MIN;
+    z[1] = F::MAX;
+
+    for q in 1..size {
+        loop {
+            let q2 = F::from_usize(q * q);
+            let vk2 = F::from_usize(v[k] * v[k]);
+            let denom = F::from_usize(2 * q - 2 * v[k]);
+            s = ((f[q] + q2) - (f[v[k]] + vk2)) / denom;
+
+            if s <= z[k] {
+                k -= 1;
+            } else {
+                k += 1;
+                v[k] = q;
+                z[k] = s;
+                z[k + 1] = F::MAX;
+
+                break;
+            }
+        }
+    }
+
+    k = 0;
+    for (q, value) in grid.iter_mut().enumerate() {
+        let qf = F::from_usize(q);
+        while z[k + 1] < qf {
+            k += 1;
+        }
+        let vkf = F::from_usize(v[k]);
+        *value = (qf - vkf) * (qf - vkf) + f[v[k]];
+        nearest(q, v[k]);
+    }
+}
+
+/// Marks pixels for which no seed has been found (yet).
+const NO_SEED: usize = usize::MAX;
+
+/// The state shared by the seed propagation algorithms: the nearest seed found so far for
+/// every pixel, and the squared distance to it.
+///
+/// A seed is any feature pixel of the input grid. The distance to a seed includes its
+/// input value, as in the exact transform.
+#[derive(Debug, Default)]
+struct SeedMap<F> {
+    width: usize,
+    seeds: Vec<usize>,
+    distances: Vec<F>,
+}
+
+impl<F: SdfFloat> SeedMap<F> {
+    fn reset(&mut self, grid: &[F], width: usize) {
+        self.width = width;
+        self.seeds.clear();
+        self.seeds.extend(
+            grid.iter()
+                .enumerate()
+                .map(|(i, value)| if *value < F::MAX { i } else { NO_SEED }),
+        );
+        self.distances.clear();
+        self.distances.extend_from_slice(grid);
+    }
+
+    /// The squared distance from pixel `p` to seed `s`.
+    #[inline]
+    fn distance(&self, grid: &[F], p: usize, s: usize) -> F {
+        let dx = (p % self.width).abs_diff(s % self.width);
+        let dy = (p / self.width).abs_diff(s / self.width);
+        F::from_usize(dx * dx + dy * dy) + grid[s]
+    }
+
+    /// Adopts the seed of pixel `n` for pixel `p` if it is closer than the current one.
+    #[inline]
+    fn offer(&mut self, grid: &[F], p: usize, n: usize) {
+        let s = self.seeds[n];
+        if s != NO_SEED {
+            let distance = self.distance(grid, p, s);
+            if distance < self.distances[p] {
+                self.distances[p] = distance;
+                self.seeds[p] = s;
+            }
+        }
+    }
+}
+
+/// The 8-point sequential signed Euclidean distance transform (8SSEDT).
+///
+/// Seeds are propagated to each pixel from its 8 neighbours in two raster scans
+/// (one forward, one backward), each of which also sweeps every row in the opposite direction.
+#[derive(Debug)]
+pub struct Ssedt8<F> {
+    map: SeedMap<F>,
+}
+
+impl<F: SdfFloat> Default for Ssedt8<F> {
+    fn default() -> Self {
+        Self {
+            map: SeedMap::default(),
+        }
+    }
+}
+
+impl<F: SdfFloat> Ssedt8<F> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<F: SdfFloat> DistanceTransform<F> for Ssedt8<F> {
+    fn transform(&mut self, grid: &mut [F], width: usize, height: usize) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let map = &mut self.map;
+        map.reset(grid, width);
+
+        for y in 0..height {
+            for x in 0..width {
+                let p = y * width + x;
+                if x > 0 {
+                    map.offer(grid, p, p - 1);
+                }
+                if y > 0 {
+                    map.offer(grid, p, p - width);
+                    if x > 0 {
+                        map.offer(grid, p, p - width - 1);
+                    }
+                    if x + 1 < width {
+                        map.offer(grid, p, p - width + 1);
+                    }
+                }
+            }
+            for x in (0..width - 1).rev() {
+                let p = y * width + x;
+                map.offer(grid, p, p + 1);
+            }
+        }
+
+        for y in (0..height).rev() {
+            for x in (0..width).rev() {
+                let p = y * width + x;
+                if x + 1 < width {
+                    map.offer(grid, p, p + 1);
+                }
+                if y + 1 < height {
+                    map.offer(grid, p, p + width);
+                    if x > 0 {
+                        map.offer(grid, p, p + width - 1);
+                    }
+                    if x + 1 < width {
+                        map.offer(grid, p, p + width + 1);
+                    }
+                }
+            }
+            for x in 1..width {
+                let p = y * width + x;
+                map.offer(grid, p, p - 1);
+            }
+        }
+
+        grid.copy_from_slice(&map.distances);
+    }
+}
+
+/// The dead reckoning distance transform described in
+/// [this paper](https://doi.org/10.1016/j.cviu.2004.05.002) by Grevera.
+///
+/// Like a chamfer distance transform, this makes a forward and a backward raster scan over
+/// the grid, deciding whether to adopt a neighbour's seed by the local (chamfer) step between
+/// the pixels. Unlike a chamfer transform, the distance recorded is the true Euclidean
+/// distance to the adopted seed.
+#[derive(Debug)]
+pub struct DeadReckoning<F> {
+    map: SeedMap<F>,
+    chamfer: Vec<F>,
+}
+
+impl<F: SdfFloat> Default for DeadReckoning<F> {
+    fn default() -> Self {
+        Self {
+            map: SeedMap::default(),
+            chamfer: Vec::new(),
+        }
+    }
+}
+
+impl<F: SdfFloat> DeadReckoning<F> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn step(&mut self, grid: &[F], p: usize, n: usize, local_distance: F) {
+        let s = self.map.seeds[n];
+        if s != NO_SEED && self.chamfer[n] + local_distance < self.chamfer[p] {
+            let distance = self.map.distance(grid, p, s);
+            self.map.seeds[p] = s;
+            self.map.distances[p] = distance;
+            self.chamfer[p] = distance.sqrt();
+        }
+    }
+}
+
+impl<F: SdfFloat> DistanceTransform<F> for DeadReckoning<F> {
+    fn transform(&mut self, grid: &mut [F], width: usize, height: usize) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        self.map.reset(grid, width);
+        self.chamfer.clear();
+        self.chamfer.extend(grid.iter().map(|value| value.sqrt()));
+
+        let one = F::ONE;
+        let diagonal = F::from_usize(2).sqrt();
+
+        for y in 0..height {
+            for x in 0..width {
+                let p = y * width + x;
+                if y > 0 {
+                    if x > 0 {
+                        self.step(grid, p, p - width - 1, diagonal);
+                    }
+                    self.step(grid, p, p - width, one);
+                    if x + 1 < width {
+                        self.step(grid, p, p - width + 1, diagonal);
+                    }
+                }
+                if x > 0 {
+                    self.step(grid, p, p - 1, one);
+                }
+            }
+        }
+
+        for y in (0..height).rev() {
+            for x in (0..width).rev() {
+                let p = y * width + x;
+                if x + 1 < width {
+                    self.step(grid, p, p + 1, one);
+                }
+                if y + 1 < height {
+                    if x > 0 {
+                        self.step(grid, p, p + width - 1, diagonal);
+                    }
+                    self.step(grid, p, p + width, one);
+                    if x + 1 < width {
+                        self.step(grid, p, p + width + 1, diagonal);
+                    }
+                }
+            }
+        }
+
+        grid.copy_from_slice(&self.map.distances);
+    }
+}
+
+/// The jump flooding distance transform described by Rong & Tan.
+///
+/// Seeds are propagated between pixels at halving distances (N/2, N/4, ..., 1), followed by a
+/// final pass at a distance of one pixel which fixes most of the remaining errors (JFA+1).
+/// Every pass is independent per pixel, which makes this algorithm popular on GPUs,
+/// but on a CPU it does `O(n log n)` work.
+#[derive(Debug)]
+pub struct JumpFlooding<F> {
+    map: SeedMap<F>,
+    next: SeedMap<F>,
+}
+
+impl<F: SdfFloat> Default for JumpFlooding<F> {
+    fn default() -> Self {
+        Self {
+            map: SeedMap::default(),
+            next: SeedMap::default(),
+        }
+    }
+}
+
+impl<F: SdfFloat> JumpFlooding<F> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn pass(&mut self, grid: &[F], width: usize, height: usize, step: usize) {
+        self.next.width = width;
+        self.next.seeds.clone_from(&self.map.seeds);
+        self.next.distances.clone_from(&self.map.distances);
+
+        for y in 0..height {
+            for x in 0..width {
+                let p = y * width + x;
+                for (dx, dy) in [
+                    (-1, -1),
+                    (0, -1),
+                    (1, -1),
+                    (-1, 0),
+                    (1, 0),
+                    (-1, 1),
+                    (0, 1),
+                    (1, 1),
+                ] {
+                    let nx = x.checked_add_signed(dx * step as isize);
+                    let ny = y.checked_add_signed(dy * step as isize);
+                    if let (Some(nx), Some(ny)) = (nx, ny) {
+                        if nx < width && ny < height {
+                            let s = self.map.seeds[ny * width + nx];
+                            if s != NO_SEED {
+                                let distance = self.map.distance(grid, p, s);
+                                if distance < self.next.distances[p] {
+                                    self.next.distances[p] = distance;
+                                    self.next.seeds[p] = s;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        std::mem::swap(&mut self.map, &mut self.next);
+    }
+}
+
+impl<F: SdfFloat> DistanceTransform<F> for JumpFlooding<F> {
+    fn transform(&mut self, grid: &mut [F], width: usize, height: usize) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        self.map.reset(grid, width);
+
+        let mut step = width.max(height).next_power_of_two() / 2;
+        while step > 0 {
+            self.pass(grid, width, height, step);
+            step /= 2;
+        }
+        self.pass(grid, width, height, 1);
+
+        grid.copy_from_slice(&self.map.distances);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DistanceAlgorithm;
+    #[cfg(feature = "rayon")]
+    use super::{DistanceTransform, FelzenszwalbHuttenlocher};
+
+    /// Seeds the outside of the ampersand fixture (with a 3px buffer) the same way the
+    /// renderer does.
+    fn glyph_grid() -> (Vec<f64>, usize, usize) {
+        let alpha: Vec<u8> = Vec::from(include!("../fixtures/glyph_alpha.json"));
+        let grid = alpha
+            .into_iter()
+            .map(|a| {
+                if a == 0 {
+                    f64::MAX
+                } else {
+                    (0.5 - f64::from(a) / 255.0).max(0.0).powi(2)
+                }
+            })
+            .collect();
+        (grid, 22, 25)
+    }
+
+    /// A larger grid with sparse, irregular seeds.
+    fn irregular_grid(width: usize, height: usize) -> Vec<f64> {
+        (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                if (x * x + y * 3) % 97 < 4 {
+                    0.0
+                } else {
+                    f64::MAX
+                }
+            })
+            .collect()
+    }
+
+    fn brute_force(grid: &[f64], width: usize) -> Vec<f64> {
+        let seeds: Vec<_> = grid
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value < f64::MAX)
+            .collect();
+        (0..grid.len())
+            .map(|p| {
+                seeds
+                    .iter()
+                    .map(|(s, value)| {
+                        let dx = (p % width).abs_diff(s % width);
+                        let dy = (p / width).abs_diff(s / width);
+                        (dx * dx + dy * dy) as f64 + **value
+                    })
+                    .fold(f64::MAX, f64::min)
+            })
+            .collect()
+    }
+
+    /// Returns the maximum and mean error (in pixels) of an algorithm against the brute
+    /// force transform.
+    fn errors(algorithm: DistanceAlgorithm, grid: &[f64], width: usize) -> (f64, f64) {
+        let expected = brute_force(grid, width);
+        let mut actual = grid.to_vec();
+        algorithm
+            .transform()
+            .transform(&mut actual, width, grid.len() / width);
+
+        let errors: Vec<_> = expected
+            .iter()
+            .zip(actual.iter())
+            .map(|(expected, actual)| {
+                assert!(
+                    *actual >= expected - 1e-9,
+                    "Distances are never underestimated"
+                );
+                actual.sqrt() - expected.sqrt()
+            })
+            .collect();
+        let max = errors.iter().copied().fold(0.0, f64::max);
+        (max, errors.iter().sum::<f64>() / errors.len() as f64)
+    }
+
+    #[test]
+    fn test_exact() {
+        let (glyph, width, _) = glyph_grid();
+        let (max, _) = errors(DistanceAlgorithm::FelzenszwalbHuttenlocher, &glyph, width);
+        assert!(max < 1e-9);
+
+        let irregular = irregular_grid(160, 120);
+        let (max, _) = errors(DistanceAlgorithm::FelzenszwalbHuttenlocher, &irregular, 160);
+        assert!(max < 1e-9);
+    }
+
+    #[test]
+    fn test_approximate() {
+        let (glyph, width, _) = glyph_grid();
+        let irregular = irregular_grid(160, 120);
+
+        for (algorithm, max_error, mean_error) in [
+            (DistanceAlgorithm::Ssedt8, 0.05, 0.001),
+            (DistanceAlgorithm::DeadReckoning, 0.5, 0.02),
+            (DistanceAlgorithm::JumpFlooding, 0.05, 0.001),
+        ] {
+            for (grid, width) in [(&glyph, width), (&irregular, 160)] {
+                let (max, mean) = errors(algorithm, grid, width);
+                assert!(max < max_error, "{algorithm:?} max error {max}");
+                assert!(mean < mean_error, "{algorithm:?} mean error {mean}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        for algorithm in [
+            DistanceAlgorithm::FelzenszwalbHuttenlocher,
+            DistanceAlgorithm::Ssedt8,
+            DistanceAlgorithm::DeadReckoning,
+            DistanceAlgorithm::JumpFlooding,
+        ] {
+            let mut grid: Vec<f64> = Vec::new();
+            algorithm.transform().transform(&mut grid, 0, 0);
+            assert!(grid.is_empty());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_parallel_transform() {
+        // A large, irregular grid, so that every row and column is different
+        let (width, height) = (300, 200);
+        let grid = irregular_grid(width, height);
+
+        let mut serial = FelzenszwalbHuttenlocher::new();
+        serial.parallel_threshold = usize::MAX;
+        let mut parallel = FelzenszwalbHuttenlocher::new();
+        parallel.parallel_threshold = 0;
+
+        let mut serial_grid = grid.clone();
+        serial.transform(&mut serial_grid, width, height);
+        let mut parallel_grid = grid;
+        parallel.transform(&mut parallel_grid, width, height);
+
+        assert_eq!(serial_grid, parallel_grid);
+    }
+}
@@ -0,0 +1,263 @@
+//! This binary crate provides a small HTTP server for MapLibre/Mapbox-compatible glyph ranges.
+//! It serves a directory of pre-rendered PBF glyphs (such as the output of
+//! [build_pbf_glyphs](https://github.com/stadiamaps/sdf_font_tools/tree/main/build_pbf_glyphs)),
+//! combining fonts into composite fontstacks on the fly with
+//! [pbf_font_tools](https://github.com/stadiamaps/sdf_font_tools/tree/main/pbf_font_tools).
+//!
+//! ## Usage
+//!
+//! ```
+//! $ serve_pbf_glyphs /path/to/glyph_dir --bind 127.0.0.1:8080
+//! ```
+//!
+//! Glyphs are then available at `/{fontstack}/{start}-{end}.pbf`, where `fontstack` is a
+//! comma-separated list of font names, in order of precedence. A JSON list of the available
+//! fonts is served at `/fonts.json`.
+
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use axum::http::HeaderValue;
+use axum::Router;
+use clap::Parser;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use pbf_font_tools::service::{Compressor, DirectoryStore, GlyphService};
+use pbf_font_tools::FallbackRenderer;
+
+#[derive(Parser, Debug)]
+#[command(version, author, about)]
+struct Args {
+    /// Sets the directory containing the PBF glyphs (each font in a subdirectory with PBF files named like `<start>-<end>.pbf`).
+    glyph_dir: PathBuf,
+    /// Sets the address to listen on.
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    bind: SocketAddr,
+    /// Sets the `max-age` (in seconds) advertised in the `Cache-Control` header of glyph responses.
+    #[arg(long, default_value_t = 86400)]
+    max_age: u64,
+    /// Sets a directory of source fonts (TTF/OTF/TTC) to render glyph ranges from on the fly when there is no matching PBF file in the glyph directory.
+    #[arg(long)]
+    font_dir: Option<PathBuf>,
+    /// Writes glyph ranges rendered from the font directory back to the glyph directory.
+    #[arg(long, requires = "font_dir")]
+    write_back: bool,
+}
+
+/// Gzip response compression.
+struct Gzip;
+
+impl Compressor for Gzip {
+    fn encoding(&self) -> &'static str {
+        "gzip"
+    }
+
+    fn compress(&self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data)?;
+        encoder.finish()
+    }
+}
+
+/// Builds the application router.
+fn app(store: DirectoryStore, max_age: u64) -> Router {
+    let cache_control = HeaderValue::from_str(&format!("public, max-age={max_age}"))
+        .expect("Invalid Cache-Control header");
+
+    GlyphService::new(store)
+        .with_compressor(Gzip)
+        .with_cache_control(cache_control)
+        .into_router()
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+
+    let listener = tokio::net::TcpListener::bind(args.bind)
+        .await
+        .expect("Unable to bind to the requested address");
+    println!(
+        "Serving glyphs from {} on http://{}",
+        args.glyph_dir.display(),
+        args.bind
+    );
+
+    let mut store = DirectoryStore::new(&args.glyph_dir);
+    if let Some(font_dir) = &args.font_dir {
+        let (fallback, skipped) = FallbackRenderer::new()
+            .with_font_dir_lossy(font_dir)
+            .expect("Unable to scan font directory");
+        for (path, e) in skipped {
+            println!("Skipping {}: {e}", path.display());
+        }
+        let fallback = fallback.with_write_back(args.write_back);
+        println!(
+            "Rendering missing glyph ranges on the fly for {} font(s) in {}",
+            fallback.font_names().count(),
+            font_dir.display()
+        );
+        store = store.with_fallback(fallback);
+    }
+
+    axum::serve(listener, app(store, args.max_age))
+        .await
+        .expect("Server error");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::net::SocketAddr;
+    use std::path::Path;
+
+    use flate2::read::GzDecoder;
+    use pbf_font_tools::protobuf::Message;
+    use pbf_font_tools::service::DirectoryStore;
+    use pbf_font_tools::Glyphs;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::app;
+
+    struct TestResponse {
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    }
+
+    impl TestResponse {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    async fn spawn_server() -> SocketAddr {
+        let glyph_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("pbf_font_tools")
+            .join("tests")
+            .join("glyphs");
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app(DirectoryStore::new(glyph_dir), 3600))
+                .await
+                .unwrap();
+        });
+        addr
+    }
+
+    /// Sends a bare-bones HTTP/1.1 request and reads the full response.
+    async fn request(addr: SocketAddr, path: &str, extra_headers: &[(&str, &str)]) -> TestResponse {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let mut request =
+            format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n");
+        for (name, value) in extra_headers {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str("\r\n");
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).await.unwrap();
+
+        let split = raw
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .expect("Malformed response");
+        let head = String::from_utf8(raw[..split].to_vec()).unwrap();
+        let mut lines = head.split("\r\n");
+        let status = lines.next().unwrap().split(' ').nth(1).unwrap();
+
+        TestResponse {
+            status: status.parse().unwrap(),
+            headers: lines
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.to_string(), value.trim().to_string()))
+                .collect(),
+            body: raw[split + 4..].to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_combined_font_stack() {
+        let addr = spawn_server().await;
+        let response = request(addr, "/SeoulNamsan%20L,Open%20Sans%20Light/0-255.pbf", &[]).await;
+
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.header("content-type"),
+            Some("application/x-protobuf")
+        );
+        assert_eq!(
+            response.header("cache-control"),
+            Some("public, max-age=3600")
+        );
+        assert!(response.header("etag").is_some());
+        assert!(response.header("content-encoding").is_none());
+
+        let glyphs = Glyphs::parse_from_bytes(&response.body).unwrap();
+        assert_eq!(glyphs.stacks[0].glyphs.len(), 228);
+    }
+
+    #[tokio::test]
+    async fn test_not_modified() {
+        let addr = spawn_server().await;
+        let path = "/Open%20Sans%20Light/0-255.pbf";
+        let response = request(addr, path, &[]).await;
+        let etag = response.header("etag").unwrap().to_string();
+
+        let response = request(addr, path, &[("If-None-Match", &etag)]).await;
+        assert_eq!(response.status, 304);
+        assert!(response.body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gzip() {
+        let addr = spawn_server().await;
+        let path = "/Open%20Sans%20Light/0-255.pbf";
+        let plain = request(addr, path, &[]).await;
+        let compressed = request(addr, path, &[("Accept-Encoding", "gzip, deflate")]).await;
+
+        assert_eq!(compressed.status, 200);
+        assert_eq!(compressed.header("content-encoding"), Some("gzip"));
+        assert_eq!(compressed.header("etag"), plain.header("etag"));
+
+        let mut decompressed = Vec::new();
+        GzDecoder::new(compressed.body.as_slice())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, plain.body);
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let addr = spawn_server().await;
+
+        let response = request(addr, "/Open%20Sans%20Light/1-256.pbf", &[]).await;
+        assert_eq!(response.status, 400);
+
+        let response = request(addr, "/..%2F..%2Fetc/0-255.pbf", &[]).await;
+        assert_eq!(response.status, 400);
+
+        let response = request(addr, "/Comic%20Sans/0-255.pbf", &[]).await;
+        assert_eq!(response.status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_font_index() {
+        let addr = spawn_server().await;
+        let response = request(addr, "/fonts.json", &[]).await;
+
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            String::from_utf8(response.body).unwrap(),
+            r#"["Empty Light","Open Sans Light","SeoulNamsan L"]"#
+        );
+    }
+}